@@ -1,4 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
+
+use graphs::{
+    parse::{Lines, ParseError},
+    scc::kosaraju,
+};
 
 struct ImplicationGraph {
     n: usize,                        // number of vertices
@@ -6,23 +11,31 @@ struct ImplicationGraph {
 }
 
 impl ImplicationGraph {
-    fn new_from_input(input: &str) -> Self {
-        let mut lines = input.lines();
+    fn new_from_input(input: &str) -> Result<Self, ParseError> {
+        let mut lines = Lines::new(input);
 
-        let n = lines.next().unwrap().parse::<isize>().unwrap();
+        let mut header = lines.expect_line("the number of variables")?;
+        let n = header.next::<usize>("the number of variables")?;
+        header.expect_end()?;
         let mut adj: HashMap<isize, Vec<isize>> = HashMap::new();
 
-        for i in -n..=n {
-            if i == 0 {
-                continue;
-            }
+        for i in 1..=n as isize {
             adj.insert(i, Vec::new());
+            adj.insert(-i, Vec::new());
         }
 
-        lines.for_each(|line| {
-            let mut split = line.split_whitespace();
-            let a = split.next().unwrap().parse::<isize>().unwrap();
-            let b = split.next().unwrap().parse::<isize>().unwrap();
+        let expected = format!("a non-zero literal between -{} and {}", n, n);
+        for mut line in lines {
+            let mut literal = || {
+                let token = line.next_str(&expected)?;
+                match token.parse::<isize>() {
+                    Ok(x) if x != 0 && x.unsigned_abs() <= n => Ok(x),
+                    _ => Err(line.error(&expected, Some(token))),
+                }
+            };
+            let a = literal()?;
+            let b = literal()?;
+            line.expect_end()?;
 
             // This represents a disjunction, a v b
 
@@ -30,118 +43,34 @@ impl ImplicationGraph {
             adj.get_mut(&-a).unwrap().push(b);
             // If !b then a
             adj.get_mut(&-b).unwrap().push(a);
-        });
-
-        ImplicationGraph { n: n as usize, adj }
-    }
-
-    fn reversed(&self) -> ImplicationGraph {
-        let mut adj = HashMap::new();
-
-        for (vertex, neighbours) in self.adj.iter() {
-            for neighbour in neighbours.iter() {
-                let neighbour_list = adj.entry(*neighbour).or_insert(Vec::new());
-                neighbour_list.push(*vertex);
-
-                adj.entry(*vertex).or_insert(Vec::new());
-            }
         }
 
-        ImplicationGraph { n: self.n, adj }
+        Ok(ImplicationGraph { n, adj })
     }
 
-    // Copied from course two
-    fn get_scc_leaders(&self) -> HashMap<isize, isize> {
-        let reverse = self.reversed();
-
-        let mut current_time = 0;
-        let mut current_leader = None;
-
-        let mut visited = HashSet::new();
-        let mut leaders: HashMap<isize, isize> = HashMap::new();
-        let mut ordering = self.adj.keys().map(|x| *x).collect();
-
-        fn dfs_loop(
-            graph: &ImplicationGraph,
-            visited: &mut HashSet<isize>,
-            current_leader: &mut Option<isize>,
-            leaders: &mut HashMap<isize, isize>,
-            current_time: &mut usize,
-            ordering: &mut Vec<isize>,
-        ) {
-            let loop_order = ordering.clone();
-
-            for &i in loop_order.iter().rev() {
-                if !visited.contains(&i) {
-                    *current_leader = Some(i);
-                    dfs(
-                        graph,
-                        i,
-                        visited,
-                        current_leader,
-                        leaders,
-                        current_time,
-                        ordering,
-                    );
-                }
-            }
+    /// Literals 1..=n map to the vertices 0..n and -1..=-n to n..2n
+    fn index(&self, literal: isize) -> usize {
+        if literal > 0 {
+            literal as usize - 1
+        } else {
+            self.n + (-literal) as usize - 1
         }
+    }
 
-        fn dfs(
-            graph: &ImplicationGraph,
-            current_vertex: isize,
-            visited: &mut HashSet<isize>,
-            current_leader: &mut Option<isize>,
-            leaders: &mut HashMap<isize, isize>,
-            current_time: &mut usize,
-            ordering: &mut Vec<isize>,
-        ) {
-            visited.insert(current_vertex);
-            leaders.insert(current_vertex, current_leader.unwrap());
-
-            if graph.adj.contains_key(&current_vertex) {
-                for &neighbour in graph.adj[&current_vertex].iter() {
-                    if !visited.contains(&neighbour) {
-                        dfs(
-                            graph,
-                            neighbour,
-                            visited,
-                            current_leader,
-                            leaders,
-                            current_time,
-                            ordering,
-                        );
-                    }
-                }
-            }
-
-            ordering[*current_time] = current_vertex;
-            *current_time += 1
+    fn literal(&self, index: usize) -> isize {
+        if index < self.n {
+            index as isize + 1
+        } else {
+            -((index - self.n) as isize + 1)
         }
+    }
 
-        dfs_loop(
-            &reverse,
-            &mut visited,
-            &mut current_leader,
-            &mut leaders,
-            &mut current_time,
-            &mut ordering,
-        );
-
-        // we need to reset certain variables since they are shared between the two phases
-        visited = HashSet::new();
-        current_time = 0;
-
-        dfs_loop(
-            self,
-            &mut visited,
-            &mut current_leader,
-            &mut leaders,
-            &mut current_time,
-            &mut ordering,
-        );
-
-        leaders
+    fn get_scc_leaders(&self) -> HashMap<isize, isize> {
+        kosaraju(self)
+            .into_iter()
+            .enumerate()
+            .map(|(i, leader)| (self.literal(i), self.literal(leader)))
+            .collect()
     }
 
     fn is_feasible(&self) -> bool {
@@ -154,6 +83,24 @@ impl ImplicationGraph {
     }
 }
 
+impl graphs::Graph for ImplicationGraph {
+    type Weight = usize;
+
+    fn vertex_count(&self) -> usize {
+        2 * self.n
+    }
+
+    fn edge_count(&self) -> usize {
+        self.adj.values().map(|x| x.len()).sum()
+    }
+
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.adj[&self.literal(v)]
+            .iter()
+            .map(|&w| (self.index(w), 1))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs::File, io::Read};
@@ -174,7 +121,7 @@ mod tests {
 -1 -2";
 
         fn helper(input: &str, exp: bool) {
-            let g = ImplicationGraph::new_from_input(&input);
+            let g = ImplicationGraph::new_from_input(&input).unwrap();
 
             assert_eq!(g.is_feasible(), exp);
         }
@@ -183,6 +130,32 @@ mod tests {
         helper(t2, false);
     }
 
+    #[test]
+    fn malformed() {
+        let error = ImplicationGraph::new_from_input("2\n1 2\n-3 1").err();
+
+        assert_eq!(
+            error,
+            Some(ParseError::new(
+                3,
+                "a non-zero literal between -2 and 2",
+                Some("-3")
+            ))
+        );
+
+        let error = ImplicationGraph::new_from_input("2\n1 2 -1").err();
+        assert_eq!(
+            error,
+            Some(ParseError::new(2, "the end of the line", Some("-1")))
+        );
+
+        let error = ImplicationGraph::new_from_input("-2\n1 2").err();
+        assert_eq!(
+            error,
+            Some(ParseError::new(1, "the number of variables", Some("-2")))
+        );
+    }
+
     #[test]
     fn exercise() {
         let files = [
@@ -194,12 +167,14 @@ mod tests {
             "data/2sat6.txt",
         ];
 
-        for file in files {
-            let mut file = File::open(file).unwrap();
+        for name in files {
+            let mut file = File::open(name).unwrap();
             let mut input = String::new();
             file.read_to_string(&mut input).unwrap();
 
-            let g = ImplicationGraph::new_from_input(&input);
+            let g = ImplicationGraph::new_from_input(&input)
+                .map_err(|e| e.in_file(name))
+                .unwrap();
             assert_eq!(g.adj.len(), g.n * 2);
             assert_eq!(g.adj.values().map(|x| x.len()).sum::<usize>(), g.n * 2);
 
@@ -0,0 +1,214 @@
+use std::{collections::VecDeque, error::Error, fmt};
+
+use graphs::{AdjacencyList, Graph, Paths, Weight};
+
+use super::Edge;
+
+/// A cycle of negative cost, its vertices in order along the cycle starting
+/// from the smallest one.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct NegativeCycle(Vec<usize>);
+
+impl fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let vertices = self
+            .0
+            .iter()
+            .chain(self.0.first())
+            .map(|v| (v + 1).to_string())
+            .collect::<Vec<_>>();
+
+        write!(
+            f,
+            "the graph has a negative cycle: {}",
+            vertices.join(" -> ")
+        )
+    }
+}
+
+impl Error for NegativeCycle {}
+
+// Given an edge list representing a graph it returns the shortest paths from
+// `source`, or a negative cycle reachable from it
+fn bellman_ford<W: Weight>(
+    n: usize,
+    edge_list: &[Edge<W>],
+    source: usize,
+) -> Result<Paths<W>, NegativeCycle> {
+    shortest_paths(n, edge_list, &[source])
+}
+
+// Only the vertices whose distance just dropped are queued to relax their
+// edges again (SPFA), so the search stops as soon as a round changes nothing
+// instead of always running n - 1 rounds. A negative cycle shows up as a
+// cycle in the parent pointers, which are checked every n relaxations so that
+// the walk over them is paid for.
+pub(super) fn shortest_paths<W: Weight>(
+    n: usize,
+    edge_list: &[Edge<W>],
+    starts: &[usize],
+) -> Result<Paths<W>, NegativeCycle> {
+    let graph = AdjacencyList::new_from_edges(n, edge_list.iter().map(|e| (e.a, e.b, e.c)));
+    let mut paths: Paths<W> = Paths::new(n, starts);
+
+    let mut queue = starts.iter().copied().collect::<VecDeque<_>>();
+    let mut queued = vec![false; n];
+    for &start in starts {
+        queued[start] = true;
+    }
+
+    let mut relaxed = 0;
+    while let Some(u) = queue.pop_front() {
+        queued[u] = false;
+        let distance = paths.distances[u].unwrap();
+
+        for (v, c) in graph.weighted_neighbours(u) {
+            let new = distance.saturating_add(c);
+            if paths.distances[v].is_some_and(|old| old <= new) {
+                continue;
+            }
+
+            paths.distances[v] = Some(new);
+            paths.parents[v] = Some(u);
+            if !queued[v] {
+                queued[v] = true;
+                queue.push_back(v);
+            }
+
+            relaxed += 1;
+            if relaxed % n == 0 {
+                if let Some(cycle) = parent_cycle(&paths.parents) {
+                    return Err(cycle);
+                }
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+// Any cycle in the parent pointers has a negative cost
+fn parent_cycle(parents: &[Option<usize>]) -> Option<NegativeCycle> {
+    let mut walk = vec![None; parents.len()]; // the walk that first reached every vertex
+
+    for start in 0..parents.len() {
+        let mut next = Some(start);
+        while let Some(v) = next {
+            match walk[v] {
+                Some(w) if w == start => return Some(cycle_through(parents, v)),
+                Some(_) => break,
+                None => walk[v] = Some(start),
+            }
+            next = parents[v];
+        }
+    }
+
+    None
+}
+
+fn cycle_through(parents: &[Option<usize>], v: usize) -> NegativeCycle {
+    let mut cycle = vec![v];
+    let mut u = parents[v].unwrap();
+    while u != v {
+        cycle.push(u);
+        u = parents[u].unwrap();
+    }
+
+    // the parents lead backwards along the cycle
+    cycle.reverse();
+    let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+    cycle.rotate_left(smallest);
+
+    NegativeCycle(cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::generated_edges;
+    use super::super::{floyd_warshall, parse_input};
+    use super::*;
+
+    #[test]
+    fn small_test() {
+        let (n, edge_list) =
+            parse_input("5 6\n1 2 4\n1 3 2\n3 2 -3\n2 4 1\n4 5 -2\n3 5 4\n").unwrap();
+
+        let paths = bellman_ford(n, &edge_list, 0).unwrap();
+        assert_eq!(
+            paths.distances,
+            vec![Some(0), Some(-1), Some(2), Some(0), Some(-2)]
+        );
+        assert_eq!(paths.path_to(4), Some(vec![0, 2, 1, 3, 4]));
+
+        let paths = bellman_ford(n, &edge_list, 3).unwrap();
+        assert_eq!(paths.distances, vec![None, None, None, Some(0), Some(-2)]);
+    }
+
+    #[test]
+    fn negative_cycle_test() {
+        // 2 -> 3 -> 4 -> 2 costs -1, and only 1 and the cycle itself reach it
+        let (n, edge_list) = parse_input("5 5\n1 2 5\n2 3 1\n3 4 -4\n4 2 2\n5 1 1\n").unwrap();
+
+        let cycle = bellman_ford(n, &edge_list, 0).unwrap_err();
+        assert_eq!(cycle, NegativeCycle(vec![1, 2, 3]));
+        assert_eq!(
+            cycle.to_string(),
+            "the graph has a negative cycle: 2 -> 3 -> 4 -> 2"
+        );
+
+        let cycle = bellman_ford(n, &edge_list, 3).unwrap_err();
+        assert_eq!(cycle, NegativeCycle(vec![1, 2, 3]));
+
+        let cycle = bellman_ford(n, &edge_list, 4).unwrap_err();
+        assert_eq!(cycle, NegativeCycle(vec![1, 2, 3]));
+
+        // a self loop is a cycle too
+        let (n, edge_list) = parse_input("2 2\n1 2 1\n2 2 -1\n").unwrap();
+        assert_eq!(bellman_ford(n, &edge_list, 0), Err(NegativeCycle(vec![1])));
+    }
+
+    #[test]
+    fn unreachable_cycle_test() {
+        let (n, edge_list) = parse_input("4 4\n1 2 3\n3 4 -2\n4 3 1\n4 2 1\n").unwrap();
+
+        let paths = bellman_ford(n, &edge_list, 0).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(3), None, None]);
+        assert!(bellman_ford(n, &edge_list, 2).is_err());
+    }
+
+    #[test]
+    fn generated_test() {
+        for seed in 0..20 {
+            let edge_list = generated_edges(30, 0.1, 4, seed);
+
+            match floyd_warshall(30, generated_edges(30, 0.1, 4, seed)) {
+                Some(cm) => {
+                    for source in 0..30 {
+                        let paths = bellman_ford(30, &edge_list, source).unwrap();
+                        for v in 0..30 {
+                            let expected = Some(cm.get(source, v)).filter(|&d| d != isize::MAX);
+                            assert_eq!(paths.distance(v), expected);
+                        }
+                    }
+                }
+                None => {
+                    let starts = (0..30).collect::<Vec<_>>();
+                    let NegativeCycle(cycle) = shortest_paths(30, &edge_list, &starts).unwrap_err();
+
+                    let cost = (0..cycle.len())
+                        .map(|i| {
+                            let (a, b) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+                            edge_list
+                                .iter()
+                                .filter(|e| e.a == a && e.b == b)
+                                .map(|e| e.c)
+                                .min()
+                                .unwrap()
+                        })
+                        .sum::<isize>();
+                    assert!(cost < 0);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::{ops::Sub, thread};
+
+use graphs::{dijkstra::dijkstras_algorithm, AdjacencyList, Weight};
+
+use super::{bellman_ford::shortest_paths, CostMatrix, Edge};
+
+// Given an edge list representing a graph it returns the same CostMatrix as
+// `floyd_warshall`, in O(nm log n) instead of Θ(n³), which is much less on
+// sparse graphs
+fn johnson<W>(n: usize, edge_list: Vec<Edge<W>>) -> Option<CostMatrix<W>>
+where
+    W: Weight + Sub<Output = W> + Send + Sync,
+{
+    johnson_parallel(n, edge_list, 1)
+}
+
+// Same as `johnson` but runs the searches from the sources on `threads`
+// threads, each filling in its own block of rows
+pub(super) fn johnson_parallel<W>(
+    n: usize,
+    edge_list: Vec<Edge<W>>,
+    threads: usize,
+) -> Option<CostMatrix<W>>
+where
+    W: Weight + Sub<Output = W> + Send + Sync,
+{
+    // the distances from a new vertex with a zero cost edge to every other
+    // one, which is what starting from all of them at distance zero comes to
+    let starts = (0..n).collect::<Vec<_>>();
+    let potentials = shortest_paths(n, &edge_list, &starts)
+        .ok()?
+        .distances
+        .into_iter()
+        .map(Option::unwrap)
+        .collect::<Vec<_>>();
+
+    // every path between two vertices changes by the same amount, and no edge
+    // is negative any more (clamped, in case floats round below zero)
+    let graph = AdjacencyList::new_from_edges(
+        n,
+        edge_list.iter().map(|e| {
+            let c = e.c.saturating_add(potentials[e.a]) - potentials[e.b];
+            (e.a, e.b, W::max(c, W::ZERO))
+        }),
+    );
+
+    let mut cm = CostMatrix::new(n);
+    let rows = n.div_ceil(threads.max(1)).max(1);
+    thread::scope(|scope| {
+        for (block, mat) in cm.mat.chunks_mut((rows * n).max(1)).enumerate() {
+            let graph = &graph;
+            let potentials = &potentials;
+
+            scope.spawn(move || {
+                for (i, row) in mat.chunks_mut(n).enumerate() {
+                    let source = block * rows + i;
+                    for (v, d) in dijkstras_algorithm(graph, source).reached() {
+                        row[v] = d.saturating_add(potentials[v]) - potentials[source];
+                    }
+                }
+            });
+        }
+    });
+
+    Some(cm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{float_edges, generated_edges};
+    use super::super::{floyd_warshall, minimum_shortest_path, parse_input};
+    use super::*;
+    use graphs::weight::OrderedFloat;
+
+    #[test]
+    fn small_test() {
+        let input = "4 5\n1 2 3\n2 3 -2\n3 4 1\n1 4 5\n4 1 2\n";
+
+        let (n, edge_list) = parse_input(input).unwrap();
+        let cm = johnson(n, edge_list).unwrap();
+        let (n, edge_list) = parse_input(input).unwrap();
+        assert_eq!(cm.mat, floyd_warshall(n, edge_list).unwrap().mat);
+
+        let shortest = minimum_shortest_path(&Some(cm)).unwrap();
+        assert_eq!((shortest.a, shortest.b, shortest.c), (1, 2, -2));
+
+        let (n, edge_list) = parse_input("3 3\n1 2 1\n2 3 -3\n3 1 1\n").unwrap();
+        assert!(johnson(n, edge_list).is_none());
+    }
+
+    #[test]
+    fn float_test() {
+        let cm = johnson_parallel(3, float_edges(), 2).unwrap();
+
+        assert_eq!(cm.get(0, 2), OrderedFloat(0.25));
+        assert_eq!(cm.get(1, 2), OrderedFloat(-0.25));
+        assert_eq!(cm.get(2, 0), OrderedFloat::<f64>::INFINITY);
+    }
+
+    #[test]
+    fn generated_test() {
+        for seed in 0..20 {
+            let edges = || generated_edges(40, 0.08, 3, seed);
+
+            let expected = floyd_warshall(40, edges());
+            for threads in [1, 3, 8, 64] {
+                let cm = johnson_parallel(40, edges(), threads);
+                assert_eq!(
+                    cm.map(|cm| cm.mat),
+                    expected.as_ref().map(|cm| cm.mat.clone())
+                );
+            }
+        }
+    }
+}
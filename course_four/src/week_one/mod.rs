@@ -1,43 +1,51 @@
+mod bellman_ford;
+mod johnson;
+
 use std::{fs::File, io::Read};
 
+use graphs::{
+    parse::{Lines, ParseError},
+    Weight,
+};
+
 #[derive(Debug)]
-struct Edge {
+struct Edge<W = isize> {
     a: usize, // tail
     b: usize, // head
-    c: isize, // cost
+    c: W,     // cost
 }
 
 #[derive(Debug)]
-struct CostMatrix {
-    n: usize,        // number of vertices
-    mat: Vec<isize>, // the matrix represented as a flat row-major vector
+struct CostMatrix<W = isize> {
+    n: usize,    // number of vertices
+    mat: Vec<W>, // the matrix represented as a flat row-major vector
 }
 
-impl CostMatrix {
+impl<W: Weight> CostMatrix<W> {
     fn new(n: usize) -> Self {
         Self {
             n,
-            mat: vec![isize::MAX; n * n],
+            mat: vec![W::INFINITY; n * n],
         }
     }
 
-    fn get(&self, i: usize, j: usize) -> isize {
+    fn get(&self, i: usize, j: usize) -> W {
         self.mat[i * self.n + j]
     }
 
-    fn set(&mut self, i: usize, j: usize, val: isize) {
+    fn set(&mut self, i: usize, j: usize, val: W) {
         self.mat[i * self.n + j] = val;
     }
 }
 
 // Given an edge list representing a graph it returns the final CostMatrix
-fn floyd_warshall(n: usize, edge_list: Vec<Edge>) -> Option<CostMatrix> {
+fn floyd_warshall<W: Weight>(n: usize, edge_list: Vec<Edge<W>>) -> Option<CostMatrix<W>> {
     let mut prev = CostMatrix::new(n);
 
     for i in 0..n {
         for j in 0..n {
             if i == j {
-                prev.set(i, j, 0);
+                prev.set(i, j, W::ZERO);
             }
         }
     }
@@ -53,7 +61,7 @@ fn floyd_warshall(n: usize, edge_list: Vec<Edge>) -> Option<CostMatrix> {
                 new.set(
                     i,
                     j,
-                    isize::min(
+                    W::min(
                         prev.get(i, j),
                         prev.get(i, k).saturating_add(prev.get(k, j)),
                     ),
@@ -65,16 +73,16 @@ fn floyd_warshall(n: usize, edge_list: Vec<Edge>) -> Option<CostMatrix> {
 
     for i in 0..n {
         for j in 0..n {
-            if i == j && prev.get(i, j) < 0 {
+            if i == j && prev.get(i, j) < W::ZERO {
                 return None;
             }
         }
     }
 
-    return Some(prev);
+    Some(prev)
 }
 
-fn minimum_shortest_path(cm: &Option<CostMatrix>) -> Option<Edge> {
+fn minimum_shortest_path<W: Weight>(cm: &Option<CostMatrix<W>>) -> Option<Edge<W>> {
     if cm.is_none() {
         return None;
     }
@@ -84,7 +92,7 @@ fn minimum_shortest_path(cm: &Option<CostMatrix>) -> Option<Edge> {
     let mut res = Edge {
         a: usize::MAX,
         b: usize::MAX,
-        c: isize::MAX,
+        c: W::INFINITY,
     };
 
     for i in 0..cm.n {
@@ -104,46 +112,120 @@ fn minimum_shortest_path(cm: &Option<CostMatrix>) -> Option<Edge> {
     Some(res)
 }
 
-fn parse_input(input: &str) -> (usize, Vec<Edge>) {
-    let mut lines = input.lines();
+fn parse_input(input: &str) -> Result<(usize, Vec<Edge>), ParseError> {
+    let mut lines = Lines::new(input);
 
-    let mut first_line_split = lines.next().unwrap().split_whitespace();
-    let n = first_line_split.next().unwrap().parse::<usize>().unwrap();
+    let mut header = lines.expect_line("the number of vertices and edges")?;
+    let n = header.next::<usize>("the number of vertices")?;
+    let m = header.next::<usize>("the number of edges")?;
+    header.expect_end()?;
 
-    let edge_list = lines
-        .map(|line| {
-            let mut split = line.split_whitespace();
-            let a = split.next().unwrap().parse::<usize>().unwrap() - 1;
-            let b = split.next().unwrap().parse::<usize>().unwrap() - 1;
-            let c = split.next().unwrap().parse::<isize>().unwrap();
+    let edge_list = (0..m)
+        .map(|_| {
+            let mut line = lines.expect_line("an edge")?;
+            let a = line.vertex_below(n)?;
+            let b = line.vertex_below(n)?;
+            let c = line.next::<isize>("an edge cost")?;
+            line.expect_end()?;
 
-            Edge { a, b, c }
+            Ok(Edge { a, b, c })
         })
-        .collect();
+        .collect::<Result<Vec<Edge>, ParseError>>()?;
+    lines.expect_end()?;
 
-    (n, edge_list)
+    Ok((n, edge_list))
 }
 
-fn parse_file(name: &str) -> (usize, Vec<Edge>) {
-    let mut file = File::open(format!("data/{}.txt", name)).unwrap();
+fn parse_file(name: &str) -> Result<(usize, Vec<Edge>), ParseError> {
+    let path = format!("data/{}.txt", name);
+    let mut file = File::open(&path).unwrap();
     let mut input = String::new();
     file.read_to_string(&mut input).unwrap();
 
-    parse_input(&input)
+    parse_input(&input).map_err(|e| e.in_file(&path))
 }
 
 #[cfg(test)]
 mod tests {
+    use super::johnson::johnson_parallel;
     use super::*;
+    use graphs::{generate, weight::OrderedFloat, Graph};
+
+    /// Three vertices, the cheapest path from the first to the last going
+    /// through a negative float cost.
+    pub(super) fn float_edges() -> Vec<Edge<OrderedFloat<f64>>> {
+        [(0, 1, 0.5), (1, 2, -0.25), (0, 2, 0.5)]
+            .into_iter()
+            .map(|(a, b, c)| Edge {
+                a,
+                b,
+                c: OrderedFloat(c),
+            })
+            .collect()
+    }
+
+    /// A random directed graph with costs between 1 and 20, shifted down by
+    /// `shift` so that some of the graphs have negative cycles.
+    pub(super) fn generated_edges(n: usize, p: f64, shift: isize, seed: u64) -> Vec<Edge> {
+        generate::with_random_weights(&generate::gnp(n, p, true, seed), 20, seed)
+            .edges()
+            .map(|(a, b, c)| Edge {
+                a,
+                b,
+                c: c as isize - shift,
+            })
+            .collect()
+    }
 
     #[test]
-    fn exercise() {
-        let (n1, edge_list1) = parse_file("graph1");
-        let (n2, edge_list2) = parse_file("graph2");
-        let (n3, edge_list3) = parse_file("graph3");
+    fn small_test() {
+        let (n, edge_list) = parse_input("4 5\n1 2 3\n2 3 -2\n3 4 1\n1 4 5\n4 1 2\n").unwrap();
+        let cm = floyd_warshall(n, edge_list);
+        assert_eq!(cm.as_ref().unwrap().get(0, 3), 2);
+        // 3 can't reach 2 without going round through 1
+        assert_eq!(cm.as_ref().unwrap().get(2, 1), 6);
+
+        let shortest = minimum_shortest_path(&cm).unwrap();
+        assert_eq!((shortest.a, shortest.b, shortest.c), (1, 2, -2));
+
+        let (n, edge_list) = parse_input("3 3\n1 2 1\n2 3 -3\n3 1 1\n").unwrap();
+        assert!(floyd_warshall(n, edge_list).is_none());
+
+        assert_eq!(
+            parse_input("3 1\n1 2 1 4\n").err(),
+            Some(ParseError::new(2, "the end of the line", Some("4")))
+        );
+        assert_eq!(
+            parse_input("3 2\n1 2 1\n").err(),
+            Some(ParseError::new(3, "an edge", None))
+        );
+    }
 
-        dbg!(1, minimum_shortest_path(&floyd_warshall(n1, edge_list1)));
-        dbg!(2, minimum_shortest_path(&floyd_warshall(n2, edge_list2)));
-        dbg!(3, minimum_shortest_path(&floyd_warshall(n3, edge_list3)));
+    #[test]
+    fn float_test() {
+        let cm = floyd_warshall(3, float_edges()).unwrap();
+
+        assert_eq!(cm.get(0, 2), OrderedFloat(0.25));
+        assert_eq!(cm.get(2, 0), OrderedFloat::<f64>::INFINITY);
+    }
+
+    #[test]
+    fn exercise() {
+        let (n1, edge_list1) = parse_file("graph1").unwrap();
+        let (n2, edge_list2) = parse_file("graph2").unwrap();
+        let (n3, edge_list3) = parse_file("graph3").unwrap();
+
+        dbg!(
+            1,
+            minimum_shortest_path(&johnson_parallel(n1, edge_list1, 4))
+        );
+        dbg!(
+            2,
+            minimum_shortest_path(&johnson_parallel(n2, edge_list2, 4))
+        );
+        dbg!(
+            3,
+            minimum_shortest_path(&johnson_parallel(n3, edge_list3, 4))
+        );
     }
 }
@@ -1,3 +1,5 @@
+use graphs::parse::{Lines, ParseError};
+
 #[derive(Debug, Clone)]
 struct Point {
     i: usize,
@@ -9,20 +11,28 @@ fn euclidean_squared(a: &Point, b: &Point) -> f64 {
     ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
 }
 
-fn parse_input(input: &str) -> Vec<Point> {
-    input
-        .lines()
-        .skip(1)
-        .map(|line| {
-            let mut split = line.split_whitespace();
-
-            Point {
-                i: split.next().unwrap().parse::<usize>().unwrap() - 1,
-                x: split.next().unwrap().parse::<f64>().unwrap(),
-                y: split.next().unwrap().parse::<f64>().unwrap(),
-            }
+fn parse_input(input: &str) -> Result<Vec<Point>, ParseError> {
+    let mut lines = Lines::new(input);
+    let mut header = lines.expect_line("the number of cities")?;
+    let n = header.next::<usize>("the number of cities")?;
+    header.expect_end()?;
+
+    let points = (0..n)
+        .map(|_| {
+            let mut line = lines.expect_line("a city")?;
+            let point = Point {
+                i: line.vertex()?,
+                x: line.next::<f64>("an x coordinate")?,
+                y: line.next::<f64>("a y coordinate")?,
+            };
+            line.expect_end()?;
+
+            Ok(point)
         })
-        .collect()
+        .collect::<Result<Vec<Point>, ParseError>>()?;
+    lines.expect_end()?;
+
+    Ok(points)
 }
 
 fn tsp(points: &[Point]) -> f64 {
@@ -81,7 +91,7 @@ mod tests {
     #[test]
     fn test() {
         fn helper(input: &str, exp: f64) {
-            let points = parse_input(input);
+            let points = parse_input(input).unwrap();
             let res = tsp(&points);
             dbg!(res);
 
@@ -89,6 +99,11 @@ mod tests {
         }
 
         helper(T1, 15.2361);
+
+        assert_eq!(
+            parse_input("x\n1 0 0").err(),
+            Some(ParseError::new(1, "the number of cities", Some("x")))
+        );
     }
 
     #[test]
@@ -97,7 +112,9 @@ mod tests {
         let mut input = String::new();
         file.read_to_string(&mut input).unwrap();
 
-        let points = parse_input(&input);
+        let points = parse_input(&input)
+            .map_err(|e| e.in_file("data/nn.txt"))
+            .unwrap();
         let res = tsp(&points);
         dbg!(res);
     }
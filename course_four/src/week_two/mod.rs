@@ -1,3 +1,5 @@
+use graphs::parse::{Lines, ParseError};
+
 #[derive(Debug)]
 struct Point {
     x: f32,
@@ -40,19 +42,27 @@ fn generate_setss(n: usize) -> Vec<Vec<Set>> {
     res
 }
 
-fn parse_input(input: &str) -> Vec<Point> {
-    input
-        .lines()
-        .skip(1)
-        .map(|line| {
-            let mut split = line.split_whitespace();
-
-            Point {
-                x: split.next().unwrap().parse::<f32>().unwrap(),
-                y: split.next().unwrap().parse::<f32>().unwrap(),
-            }
+fn parse_input(input: &str) -> Result<Vec<Point>, ParseError> {
+    let mut lines = Lines::new(input);
+    let mut header = lines.expect_line("the number of cities")?;
+    let n = header.next::<usize>("the number of cities")?;
+    header.expect_end()?;
+
+    let points = (0..n)
+        .map(|_| {
+            let mut line = lines.expect_line("a city")?;
+            let point = Point {
+                x: line.next::<f32>("an x coordinate")?,
+                y: line.next::<f32>("a y coordinate")?,
+            };
+            line.expect_end()?;
+
+            Ok(point)
         })
-        .collect()
+        .collect::<Result<Vec<Point>, ParseError>>()?;
+    lines.expect_end()?;
+
+    Ok(points)
 }
 
 fn distance(a: &Point, b: &Point) -> f32 {
@@ -183,9 +193,14 @@ mod tests {
             assert!((res - exp).abs() < 0.1)
         }
 
-        helper(tsp(&parse_input(T1)), 10.24);
-        helper(tsp(&parse_input(T2)), 12.36);
-        helper(tsp(&parse_input(T3)), 14.00);
+        helper(tsp(&parse_input(T1).unwrap()), 10.24);
+        helper(tsp(&parse_input(T2).unwrap()), 12.36);
+        helper(tsp(&parse_input(T3).unwrap()), 14.00);
+
+        assert_eq!(
+            parse_input("2\n0 0").err(),
+            Some(ParseError::new(3, "a city", None))
+        );
     }
 
     #[test]
@@ -195,7 +210,9 @@ mod tests {
 
         file.read_to_string(&mut input).unwrap();
 
-        let points = parse_input(&input);
+        let points = parse_input(&input)
+            .map_err(|e| e.in_file("data/tsp.txt"))
+            .unwrap();
         // dbg!(&points);
 
         dbg!(tsp(&points));
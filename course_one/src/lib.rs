@@ -1,5 +1,4 @@
 #![allow(dead_code)]
-#![feature(int_log)]
 
 mod week_four;
 mod week_one;
@@ -1,4 +1,13 @@
-use rand::Rng;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use graphs::{
+    parse::{Indexed, Lines, ParseError, Vertices},
+    LabeledGraph, SymbolTable,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Graph {
@@ -6,23 +15,67 @@ pub struct Graph {
 }
 
 impl Graph {
-    pub fn new_from_str(inp: &str) -> Graph {
+    /// Every line is a vertex followed by its neighbours, the vertices
+    /// should be listed in order.
+    pub fn new_from_str(inp: &str) -> Result<Graph, ParseError> {
+        Self::parse(inp, &mut Indexed)
+    }
+
+    /// Same as `new_from_str` but the vertices can be any labels, and every
+    /// label should have a line of its own.
+    pub fn new_labeled_from_str(inp: &str) -> Result<LabeledGraph<Graph>, ParseError> {
+        // the vertex of the n-th line gets the index n
+        let mut symbols = SymbolTable::new();
+        for mut line in Lines::new(inp) {
+            line.vertex_with(&mut symbols)?;
+        }
+
+        let graph = Self::parse(inp, &mut symbols)?;
+
+        Ok(LabeledGraph::new(graph, symbols))
+    }
+
+    fn parse<V: Vertices>(inp: &str, vertices: &mut V) -> Result<Graph, ParseError> {
         let mut adj = Vec::new();
-        inp.lines().for_each(|line| {
-            let mut iter = line
-                .split_ascii_whitespace()
-                .map(|s| s.parse::<usize>().unwrap() - 1);
+        for mut line in Lines::new(inp) {
+            let token = line.next_str(vertices.expected())?;
+            let index = vertices.vertex(token);
+            if index != Some(adj.len()) {
+                return Err(line.error(&format!("vertex {}", adj.len() + 1), Some(token)));
+            }
 
-            let index = iter
-                .next()
-                .expect("Every line should have at least one element");
+            let mut list = Vec::new();
+            while !line.is_empty() {
+                list.push(line.vertex_with(vertices)?);
+            }
+            adj.push(list);
+        }
 
-            assert_eq!(adj.len(), index as usize);
+        // every edge is stored once at each end, so the times `u` lists `v`
+        // less the times `v` lists `u` should come to zero
+        let mut balance: HashMap<(usize, usize), isize> = HashMap::new();
+        for (u, list) in adj.iter().enumerate() {
+            for &v in list.iter().filter(|&&v| v != u) {
+                *balance.entry((u.min(v), u.max(v))).or_default() += if u < v { 1 } else { -1 };
+            }
+        }
 
-            adj.push(iter.collect::<Vec<usize>>());
-        });
+        for (u, mut line) in Lines::new(inp).enumerate() {
+            let label = line.next_str(vertices.expected())?;
+            while !line.is_empty() {
+                let token = line.next_str(vertices.expected())?;
+                let v = vertices.vertex(token).unwrap();
+                if v >= adj.len() {
+                    return Err(line.error("a vertex that has a line of its own", Some(token)));
+                }
+                if balance.get(&(u.min(v), u.max(v))).is_some_and(|&b| b != 0) {
+                    let expected = format!("a vertex that lists {} back", label);
+                    return Err(line.error(&expected, Some(token)));
+                }
+            }
+        }
 
-        Graph { adj }
+        Ok(Graph { adj })
     }
 
     pub fn get_edge_vertices(&self, i: usize) -> (usize, usize) {
@@ -40,6 +93,11 @@ impl Graph {
 
     pub fn contract_edge(&mut self, i: usize) {
         let (a, b) = self.get_edge_vertices(i);
+        self.contract(a, b);
+    }
+
+    /// Merges the larger of `a` and `b` into the smaller one.
+    fn contract(&mut self, a: usize, b: usize) {
         let (a, b) = (usize::min(a, b), usize::max(a, b));
 
         let b_list = self.adj.remove(b);
@@ -57,19 +115,181 @@ impl Graph {
         });
     }
 
-    pub fn kargers_min_cut(&mut self) -> usize {
-        let mut rng = rand::thread_rng();
-        while self.adj.len() > 2 {
-            let m = self.adj.iter().map(|x| x.len()).sum();
+    pub fn kargers_min_cut(&self) -> usize {
+        self.kargers_min_cut_with_rng(&mut rand::thread_rng())
+    }
+
+    pub fn kargers_min_cut_with_rng<R: Rng>(&self, rng: &mut R) -> usize {
+        self.kargers_min_cut_partition(rng).0
+    }
+
+    /// Same as `kargers_min_cut_with_rng` but also returns the side of the cut
+    /// every original vertex ended up on.
+    pub fn kargers_min_cut_partition<R: Rng>(&self, rng: &mut R) -> (usize, Vec<bool>) {
+        if self.adj.len() < 2 {
+            // there is nothing to cut
+            return (0, vec![true; self.adj.len()]);
+        }
+
+        let mut graph = self.clone();
+        // the original vertices merged into each remaining vertex
+        let mut groups: Vec<Vec<usize>> = (0..self.adj.len()).map(|v| vec![v]).collect();
+
+        while graph.adj.len() > 2 {
+            let m = graph.adj.iter().map(|x| x.len()).sum();
+            if m == 0 {
+                // the graph is disconnected, so the first vertex alone is a cut
+                break;
+            }
             let rand = rng.gen_range(0..m);
 
-            self.contract_edge(rand);
+            let (a, b) = graph.get_edge_vertices(rand);
+            let (a, b) = (usize::min(a, b), usize::max(a, b));
+            let b_group = groups.remove(b);
+            groups[a].extend(b_group);
+
+            graph.contract(a, b);
         }
 
-        // because each edge is represented twice, once in the 
-        // adjacency list of each of its vertices and thus each 
+        let mut side = vec![false; self.adj.len()];
+        for &v in groups[0].iter() {
+            side[v] = true;
+        }
+
+        // because each edge is represented twice, once in the
+        // adjacency list of each of its vertices and thus each
         // crossing edge is counted twice.
-        self.adj.iter().flatten().count() / 2
+        (graph.adj.iter().flatten().count() / 2, side)
+    }
+
+    /// Runs as many independent contractions as `config.delta` asks for,
+    /// split across `config.threads` threads.
+    pub fn kargers_min_cut_trials(&self, config: &TrialConfig) -> TrialReport {
+        let trials = trials_for_confidence(self.adj.len(), config.delta);
+        let threads = config.threads.clamp(1, trials);
+        let done = AtomicBool::new(false);
+
+        let reports = std::thread::scope(|s| {
+            let handles = (0..threads)
+                .map(|t| {
+                    // the first `trials % threads` threads pick up the remainder
+                    let share = trials / threads + usize::from(t < trials % threads);
+                    let done = &done;
+
+                    s.spawn(move || {
+                        let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(t as u64));
+                        let mut report = TrialReport {
+                            min_cut: usize::MAX,
+                            hits: 0,
+                            trials: 0,
+                        };
+
+                        for _ in 0..share {
+                            if done.load(Ordering::Relaxed) {
+                                break;
+                            }
+
+                            let cut = self.kargers_min_cut_with_rng(&mut rng);
+                            report.record(cut);
+
+                            if config.lower_bound.is_some_and(|lb| cut <= lb) {
+                                done.store(true, Ordering::Relaxed);
+                            }
+                        }
+
+                        report
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap())
+                .collect::<Vec<TrialReport>>()
+        });
+
+        reports.into_iter().reduce(|a, b| a.merge(b)).unwrap()
+    }
+}
+
+impl graphs::Graph for Graph {
+    type Weight = usize;
+
+    fn vertex_count(&self) -> usize {
+        self.adj.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.adj.iter().map(|l| l.len()).sum()
+    }
+
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.adj[v].iter().map(|&w| (w, 1))
+    }
+}
+
+/// Number of trials needed so that the probability of never finding a
+/// minimum cut is at most `delta`.
+///
+/// A single contraction finds a given minimum cut with probability at least
+/// 1 / (n(n-1)/2), so after t trials the failure probability is at most
+/// (1 - 1/C)^t <= e^(-t/C), which gives t = C ln(1/delta).
+pub fn trials_for_confidence(n: usize, delta: f64) -> usize {
+    assert!(delta > 0. && delta < 1., "delta should lie in (0, 1)");
+
+    let c = (n * n.saturating_sub(1) / 2).max(1) as f64;
+    ((c * (1. / delta).ln()).ceil() as usize).max(1)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrialConfig {
+    pub delta: f64,                 // acceptable probability of missing the minimum cut
+    pub threads: usize,             // number of worker threads
+    pub seed: u64,                  // thread i seeds its rng with seed + i
+    pub lower_bound: Option<usize>, // stop as soon as a cut this small is found
+}
+
+impl TrialConfig {
+    pub fn new(delta: f64) -> Self {
+        Self {
+            delta,
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            seed: 0,
+            lower_bound: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrialReport {
+    pub min_cut: usize, // smallest cut seen
+    pub hits: usize,    // number of trials that produced min_cut
+    pub trials: usize,  // number of trials actually run
+}
+
+impl TrialReport {
+    fn record(&mut self, cut: usize) {
+        self.trials += 1;
+        if cut < self.min_cut {
+            self.min_cut = cut;
+            self.hits = 1;
+        } else if cut == self.min_cut {
+            self.hits += 1;
+        }
+    }
+
+    fn merge(self, other: TrialReport) -> TrialReport {
+        let hits = match self.min_cut.cmp(&other.min_cut) {
+            std::cmp::Ordering::Less => self.hits,
+            std::cmp::Ordering::Equal => self.hits + other.hits,
+            std::cmp::Ordering::Greater => other.hits,
+        };
+
+        TrialReport {
+            min_cut: usize::min(self.min_cut, other.min_cut),
+            hits,
+            trials: self.trials + other.trials,
+        }
     }
 }
 
@@ -87,17 +307,52 @@ mod tests {
 
     #[test]
     fn test_new_from_str() {
-        let parsed = Graph::new_from_str(E1);
+        let parsed = Graph::new_from_str(E1).unwrap();
         let exp = Graph {
             adj: vec![vec![1, 2, 3], vec![0, 2, 3], vec![0, 1, 3], vec![0, 1, 2]],
         };
 
         assert_eq!(parsed, exp);
+        assert_eq!(graphs::Graph::edge_count(&parsed), 12);
+    }
+
+    #[test]
+    fn test_new_from_str_out_of_order() {
+        assert_eq!(
+            Graph::new_from_str("1 2\n3 1").unwrap_err(),
+            ParseError::new(2, "vertex 2", Some("3"))
+        );
+        assert_eq!(
+            Graph::new_from_str("1 5\n2 1").unwrap_err(),
+            ParseError::new(1, "a vertex that has a line of its own", Some("5"))
+        );
+        assert_eq!(
+            Graph::new_from_str("1 2 2\n2 1").unwrap_err(),
+            ParseError::new(1, "a vertex that lists 1 back", Some("2"))
+        );
+    }
+
+    #[test]
+    fn test_new_labeled_from_str() {
+        let graph = Graph::new_labeled_from_str(
+            r"lb a b
+a lb b
+b a lb",
+        )
+        .unwrap();
+
+        assert_eq!(graph.graph.adj, vec![vec![1, 2], vec![0, 2], vec![1, 0]]);
+        assert_eq!(graph.label(0), "lb");
+
+        assert_eq!(
+            Graph::new_labeled_from_str("a b\nb a c").unwrap_err(),
+            ParseError::new(2, "a vertex that has a line of its own", Some("c"))
+        );
     }
 
     #[test]
     fn test_get_edge() {
-        let graph = Graph::new_from_str(E1);
+        let graph = Graph::new_from_str(E1).unwrap();
 
         let helper = |i: usize, exp: (usize, usize)| {
             assert_eq!(graph.get_edge_vertices(i), exp);
@@ -112,7 +367,7 @@ mod tests {
 
     #[test]
     fn test_contraction() {
-        let mut graph = Graph::new_from_str(E1);
+        let mut graph = Graph::new_from_str(E1).unwrap();
         graph.contract_edge(0);
 
         let exp = Graph {
@@ -131,36 +386,122 @@ mod tests {
 7 5 6 8
 8 5 6 7";
 
-   #[test]
+    #[test]
     fn test_kargers_min_cut() {
-        let graph = Graph::new_from_str(E3);
+        let graph = Graph::new_from_str(E3).unwrap();
 
         let res = (0..graph.adj.len().pow(2))
-            .map(|_| graph.clone().kargers_min_cut())
+            .map(|_| graph.kargers_min_cut())
             .min()
             .unwrap();
 
         assert_eq!(res, 2);
     }
 
+    #[test]
+    fn test_kargers_min_cut_partition() {
+        use graphs::dot::{to_dot, DotOptions, Overlay};
+
+        let graph = Graph::new_from_str(E3).unwrap();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let (cut, side) = (0..graph.adj.len().pow(2))
+            .map(|_| graph.kargers_min_cut_partition(&mut rng))
+            .min_by_key(|&(cut, _)| cut)
+            .unwrap();
+
+        assert_eq!(cut, 2);
+        assert!(side[..4].iter().all(|&s| s == side[0]));
+        assert!(side[4..].iter().all(|&s| s != side[0]));
+
+        let dot = to_dot(
+            &graph,
+            &DotOptions {
+                overlays: vec![Overlay::Cut(&side)],
+                ..Default::default()
+            },
+        );
+        assert_eq!(dot.matches("style=dashed").count(), cut);
+    }
+
+    #[test]
+    fn test_kargers_min_cut_planted() {
+        let (generated, side) = graphs::generate::planted_partition(16, 3, 4);
+        let graph = Graph::new_from_str(&graphs::generate::to_adjacency_list(&generated)).unwrap();
+        let mut rng = StdRng::seed_from_u64(4);
+
+        let (cut, found) = (0..graph.adj.len().pow(2))
+            .map(|_| graph.kargers_min_cut_partition(&mut rng))
+            .min_by_key(|&(cut, _)| cut)
+            .unwrap();
+
+        assert_eq!(cut, 3);
+        assert!(found == side || found.iter().zip(side.iter()).all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn test_kargers_min_cut_partition_disconnected() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let graph = Graph::new_from_str("1 2\n2 1\n3 4\n4 3\n5").unwrap();
+        let (cut, side) = graph.kargers_min_cut_partition(&mut rng);
+        assert_eq!(cut, 0);
+        assert!(side.contains(&true) && side.contains(&false));
+
+        let empty = Graph { adj: Vec::new() };
+        assert_eq!(empty.kargers_min_cut_partition(&mut rng), (0, Vec::new()));
+        assert_eq!(
+            empty.kargers_min_cut_trials(&TrialConfig::new(0.1)).min_cut,
+            0
+        );
+    }
+
+    #[test]
+    fn test_trials_for_confidence() {
+        // C(8, 2) = 28 and ln(100) ~ 4.6
+        assert_eq!(trials_for_confidence(8, 0.01), 129);
+        assert_eq!(trials_for_confidence(1, 0.5), 1);
+    }
+
+    #[test]
+    fn test_kargers_min_cut_trials() {
+        let graph = Graph::new_from_str(E3).unwrap();
+
+        let config = TrialConfig {
+            seed: 42,
+            ..TrialConfig::new(0.001)
+        };
+        let report = graph.kargers_min_cut_trials(&config);
+
+        assert_eq!(report.min_cut, 2);
+        assert_eq!(report.trials, trials_for_confidence(8, 0.001));
+        assert!(report.hits >= 1 && report.hits <= report.trials);
+
+        // the same seed and thread count reproduce the same report
+        assert_eq!(graph.kargers_min_cut_trials(&config), report);
+
+        let early = graph.kargers_min_cut_trials(&TrialConfig {
+            threads: 1,
+            lower_bound: Some(2),
+            ..config
+        });
+        assert_eq!(early.min_cut, 2);
+        assert_eq!(early.hits, 1);
+        assert!(early.trials <= report.trials);
+    }
+
     #[test]
     fn exercise() {
         let mut file = File::open("./src/week_four/mincut_input.txt").unwrap();
         let mut buffer = String::new();
         file.read_to_string(&mut buffer).unwrap();
 
-        let graph = Graph::new_from_str(&buffer);
-
-        let mut res = usize::MAX;
-        for i in 0..graph.adj.len().pow(2) {
-            let curr = graph.clone().kargers_min_cut();
-            res = usize::min(res, curr);
+        let graph = Graph::new_from_str(&buffer)
+            .map_err(|e| e.in_file("./src/week_four/mincut_input.txt"))
+            .unwrap();
 
-            if i % 10 == 0 {
-                dbg!((i, curr, res));
-            }
-        }
+        let report = graph.kargers_min_cut_trials(&TrialConfig::new(0.1));
 
-        dbg!(res);
+        dbg!(report);
     }
 }
@@ -1,5 +1,4 @@
 #![allow(dead_code)]
-#![feature(map_first_last)]
 
 mod week_one;
 mod week_two;
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use graphs::parse::{Lines, ParseError};
+
 fn knapsack(v: &[usize], w: &[usize], c: usize) -> usize {
     assert_eq!(v.len(), w.len());
 
@@ -39,47 +41,40 @@ fn knapsack_top_down(v: &[usize], w: &[usize], c: usize) -> usize {
         let without_x = recurse(i - 1, x, v, w, cache);
         if w[i - 1] > x {
             cache.insert((i, x), without_x);
-            return without_x;
+            without_x
         } else {
             let with_x = recurse(i - 1, x - w[i - 1], v, w, cache) + v[i - 1];
             let result = usize::max(without_x, with_x);
 
             cache.insert((i, x), result);
-            return result;
+            result
         }
     }
 
     recurse(v.len(), c, v, w, &mut cache)
 }
 
-fn parse_input(input: &str) -> (Vec<usize>, Vec<usize>, usize) {
-    let mut lines = input.lines();
-
-    let first_line = lines.next().unwrap();
-    let c = first_line
-        .split_whitespace()
-        .next()
-        .unwrap()
-        .parse::<usize>()
-        .unwrap();
-    let n = first_line
-        .split_whitespace()
-        .next()
-        .unwrap()
-        .parse::<usize>()
-        .unwrap();
+fn parse_input(input: &str) -> Result<(Vec<usize>, Vec<usize>, usize), ParseError> {
+    let mut lines = Lines::new(input);
+
+    let mut first_line = lines.expect_line("the knapsack size and the number of items")?;
+    let c = first_line.next::<usize>("the knapsack size")?;
+    let n = first_line.next::<usize>("the number of items")?;
+    first_line.expect_end()?;
 
     let (mut v, mut w) = (Vec::with_capacity(n), Vec::with_capacity(n));
-    for line in lines {
-        let mut split = line.split_whitespace();
-        let vi = split.next().unwrap().parse::<usize>().unwrap();
-        let wi = split.next().unwrap().parse::<usize>().unwrap();
+    for _ in 0..n {
+        let mut line = lines.expect_line("an item value and weight")?;
+        let vi = line.next::<usize>("an item value")?;
+        let wi = line.next::<usize>("an item weight")?;
+        line.expect_end()?;
 
         v.push(vi);
         w.push(wi);
     }
+    lines.expect_end()?;
 
-    (v, w, c)
+    Ok((v, w, c))
 }
 
 #[cfg(test)]
@@ -101,6 +96,32 @@ mod tests {
         assert_eq!(res_top_down, 8);
     }
 
+    #[test]
+    fn test_parse_input() {
+        let (v, w, c) = parse_input("6 4\n3 4\n2 3\n4 2\n4 3").unwrap();
+
+        assert_eq!(v, vec![3, 2, 4, 4]);
+        assert_eq!(w, vec![4, 3, 2, 3]);
+        assert_eq!(c, 6);
+
+        assert_eq!(
+            parse_input("6 4\n3 4\n2").unwrap_err(),
+            ParseError::new(3, "an item weight", None)
+        );
+        assert_eq!(
+            parse_input("6 4\n3 4\n2 3\n4 2").unwrap_err(),
+            ParseError::new(5, "an item value and weight", None)
+        );
+        assert_eq!(
+            parse_input("6 2\n3 4\n2 3\n4 2").unwrap_err(),
+            ParseError::new(4, "the end of the input", Some("4"))
+        );
+        assert_eq!(
+            parse_input("6 2\n3 4 1\n2 3").unwrap_err(),
+            ParseError::new(2, "the end of the line", Some("1"))
+        );
+    }
+
     #[test]
     fn exercise_1() {
         let mut input = String::new();
@@ -109,7 +130,9 @@ mod tests {
             .read_to_string(&mut input)
             .unwrap();
 
-        let (v, w, c) = parse_input(&input);
+        let (v, w, c) = parse_input(&input)
+            .map_err(|e| e.in_file("data/knapsack.txt"))
+            .unwrap();
         let res = knapsack(&v, &w, c);
 
         dbg!(res);
@@ -123,7 +146,9 @@ mod tests {
             .read_to_string(&mut input)
             .unwrap();
 
-        let (v, w, c) = parse_input(&input);
+        let (v, w, c) = parse_input(&input)
+            .map_err(|e| e.in_file("data/knapsack_big.txt"))
+            .unwrap();
         let res = knapsack_top_down(&v, &w, c);
 
         dbg!(res);
@@ -1,4 +1,4 @@
-use std::{collections::BTreeSet, isize};
+use graphs::parse::{Lines, ParseError};
 
 #[derive(Clone, Debug, PartialEq)]
 struct Graph {
@@ -8,58 +8,44 @@ struct Graph {
 }
 
 impl Graph {
-    fn new_from_str(string: &str) -> Self {
-        let mut lines = string.lines();
-        let first = lines.next().unwrap();
+    fn new_from_str(string: &str) -> Result<Self, ParseError> {
+        let mut lines = Lines::new(string);
+        let mut first = lines.expect_line("the number of nodes and edges")?;
 
-        let mut first_split = first.split_whitespace();
-        let n = first_split.next().unwrap().parse::<usize>().unwrap();
-        let m = first_split.next().unwrap().parse::<usize>().unwrap();
+        let n = first.next::<usize>("the number of nodes")?;
+        let m = first.next::<usize>("the number of edges")?;
+        first.expect_end()?;
 
         let mut a = vec![Vec::new(); n];
-        for line in lines {
-            let mut split = line.split_whitespace();
-            let u = split.next().unwrap().parse::<usize>().unwrap() - 1;
-            let v = split.next().unwrap().parse::<usize>().unwrap() - 1;
-            let c = split.next().unwrap().parse::<isize>().unwrap();
+        for _ in 0..m {
+            let mut line = lines.expect_line("an edge")?;
+            let u = line.vertex_below(n)?;
+            let v = line.vertex_below(n)?;
+            let c = line.next::<isize>("an edge cost")?;
+            line.expect_end()?;
 
             a[u].push((v, c));
             a[v].push((u, c));
         }
+        lines.expect_end()?;
 
-        Self { n, m, a }
+        Ok(Self { n, m, a })
     }
+}
 
-    fn prims_algorithm(&self, start: usize) -> isize {
-        let mut current_keys = vec![isize::MAX; self.n];
-        current_keys[0] = isize::MIN;
-        let mut heap: BTreeSet<(isize, usize)> = BTreeSet::new(); // (key, vertex)
-
-        for &(edge_to, edge_cost) in self.a[start].iter() {
-            if current_keys[edge_to] > edge_cost {
-                heap.remove(&(current_keys[edge_to], edge_to));
-                current_keys[edge_to] = edge_cost;
-                heap.insert((current_keys[edge_to], edge_to));
-            }
-        }
+impl graphs::Graph for Graph {
+    type Weight = isize;
 
-        let mut cost = 0;
-        while !heap.is_empty() {
-            let (k, v) = heap.pop_first().unwrap();
-            // dbg!((k, v), &current_keys);
-            cost += k;
-            current_keys[v] = isize::MIN;
-
-            for &(w, c) in self.a[v].iter() {
-                if current_keys[w] > c {
-                    heap.remove(&(current_keys[w], w));
-                    current_keys[w] = c;
-                    heap.insert((current_keys[w], w));
-                }
-            }
-        }
+    fn vertex_count(&self) -> usize {
+        self.n
+    }
 
-        cost
+    fn edge_count(&self) -> usize {
+        2 * self.m // every edge is stored in the lists of both its vertices
+    }
+
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, isize)> {
+        self.a[v].iter().copied()
     }
 }
 
@@ -67,6 +53,8 @@ impl Graph {
 mod tests {
     use std::{fs::File, io::Read};
 
+    use graphs::mst::prims_algorithm;
+
     use super::*;
 
     const SMALL: &str = "4 5
@@ -78,9 +66,14 @@ mod tests {
 
     #[test]
     fn small() {
-        let graph = Graph::new_from_str(SMALL);
+        let graph = Graph::new_from_str(SMALL).unwrap();
+
+        assert_eq!(prims_algorithm(&graph, 0), 7);
 
-        assert_eq!(graph.prims_algorithm(0), 7);
+        assert_eq!(
+            Graph::new_from_str("4 1\n1 2 1\n2 3 4").unwrap_err(),
+            ParseError::new(3, "the end of the input", Some("2"))
+        );
     }
 
     #[test]
@@ -89,9 +82,11 @@ mod tests {
         let mut buffer = String::new();
         file.read_to_string(&mut buffer).unwrap();
 
-        let graph = Graph::new_from_str(&buffer);
+        let graph = Graph::new_from_str(&buffer)
+            .map_err(|e| e.in_file("data/edges.txt"))
+            .unwrap();
         // dbg!(&graph);
         dbg!("graph parsed");
-        dbg!(graph.prims_algorithm(0));
+        dbg!(prims_algorithm(&graph, 0));
     }
 }
@@ -1,3 +1,5 @@
+use graphs::parse::{Lines, ParseError};
+
 #[derive(Clone, Debug)]
 struct Job {
     weight: usize,
@@ -5,25 +7,30 @@ struct Job {
 }
 
 /// Parse the jobs.txt file into a list of jobs
-fn parse_jobs(string: &str) -> Vec<Job> {
-    let mut lines = string.lines();
-    lines.next().unwrap(); // skip the length
-
-    lines
-        .into_iter()
-        .map(|line| {
-            let mut split = line.split_whitespace();
-            let weight = split.next().unwrap().parse::<usize>().unwrap();
-            let length = split.next().unwrap().parse::<usize>().unwrap();
-
-            Job { weight, length }
+fn parse_jobs(string: &str) -> Result<Vec<Job>, ParseError> {
+    let mut lines = Lines::new(string);
+    let mut header = lines.expect_line("the number of jobs")?;
+    let n = header.next::<usize>("the number of jobs")?;
+    header.expect_end()?;
+
+    let jobs = (0..n)
+        .map(|_| {
+            let mut line = lines.expect_line("a job weight and length")?;
+            let weight = line.next::<usize>("a job weight")?;
+            let length = line.next::<usize>("a job length")?;
+            line.expect_end()?;
+
+            Ok(Job { weight, length })
         })
-        .collect()
+        .collect::<Result<Vec<Job>, ParseError>>()?;
+    lines.expect_end()?;
+
+    Ok(jobs)
 }
 
 /// Sequence the jobs using the ratio criterion
-fn sequence_jobs(jobs: &Vec<Job>) -> Vec<Job> {
-    let mut jobs = jobs.clone();
+fn sequence_jobs(jobs: &[Job]) -> Vec<Job> {
+    let mut jobs = jobs.to_vec();
 
     jobs.sort_by(|a, b| {
         let a_factor = a.weight as f64 / a.length as f64;
@@ -36,8 +43,8 @@ fn sequence_jobs(jobs: &Vec<Job>) -> Vec<Job> {
 }
 
 /// Sequence the jobs using the difference criterion
-fn sequence_jobs_wrong(jobs: &Vec<Job>) -> Vec<Job> {
-    let mut jobs = jobs.clone();
+fn sequence_jobs_wrong(jobs: &[Job]) -> Vec<Job> {
+    let mut jobs = jobs.to_vec();
 
     jobs.sort_by(|a, b| {
         let a_factor = a.weight as isize - a.length as isize;
@@ -54,7 +61,7 @@ fn sequence_jobs_wrong(jobs: &Vec<Job>) -> Vec<Job> {
 }
 
 /// Objective function is the sum of weighted completion times
-fn evaluate_objective_function(jobs: &Vec<Job>) -> usize {
+fn evaluate_objective_function(jobs: &[Job]) -> usize {
     let mut time_so_far = 0;
     let mut res = 0;
 
@@ -86,13 +93,34 @@ mod tests {
         assert_eq!(wrong_objective, 23);
     }
 
+    #[test]
+    fn test_parse_jobs() {
+        let jobs = parse_jobs("2\n3 5\n1 2").unwrap();
+        assert_eq!(evaluate_objective_function(&jobs), 22);
+
+        assert_eq!(
+            parse_jobs("abc\n3 5").unwrap_err(),
+            ParseError::new(1, "the number of jobs", Some("abc"))
+        );
+        assert_eq!(
+            parse_jobs("3\n3 5\n1 2").unwrap_err(),
+            ParseError::new(4, "a job weight and length", None)
+        );
+        assert_eq!(
+            parse_jobs("1\n3 5\n1 2").unwrap_err(),
+            ParseError::new(3, "the end of the input", Some("1"))
+        );
+    }
+
     #[test]
     fn exercise() {
         let mut file = File::open("data/jobs.txt").unwrap();
         let mut buffer = String::new();
         file.read_to_string(&mut buffer).unwrap();
 
-        let jobs = parse_jobs(&buffer);
+        let jobs = parse_jobs(&buffer)
+            .map_err(|e| e.in_file("data/jobs.txt"))
+            .unwrap();
 
         let correct = sequence_jobs(&jobs);
         let wrong = sequence_jobs_wrong(&jobs);
@@ -1,5 +1,7 @@
 use std::{cmp, collections::BinaryHeap};
 
+use graphs::parse::{Lines, ParseError};
+
 #[derive(Debug, Clone)]
 /// Every node in the huffman tree is either
 ///     - a leaf node representing an alphabet
@@ -88,12 +90,24 @@ impl Node {
 
 type Weights = Vec<usize>;
 
-fn parse_input(input: &str) -> Weights {
-    input
-        .lines()
-        .skip(1)
-        .map(|line| line.parse().unwrap())
-        .collect()
+fn parse_input(input: &str) -> Result<Weights, ParseError> {
+    let mut lines = Lines::new(input);
+    let mut header = lines.expect_line("the number of symbols")?;
+    let n = header.next::<usize>("the number of symbols")?;
+    header.expect_end()?;
+
+    let weights = (0..n)
+        .map(|_| {
+            let mut line = lines.expect_line("a symbol weight")?;
+            let weight = line.next::<usize>("a symbol weight")?;
+            line.expect_end()?;
+
+            Ok(weight)
+        })
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+    lines.expect_end()?;
+
+    Ok(weights)
 }
 
 #[cfg(test)]
@@ -109,6 +123,11 @@ mod tests {
 
         assert_eq!(tree.min_depth(), 2);
         assert_eq!(tree.max_depth(), 4);
+
+        assert_eq!(
+            parse_input("2\n3\n2\n6").unwrap_err(),
+            ParseError::new(4, "the end of the input", Some("6"))
+        );
     }
 
     #[test]
@@ -124,7 +143,7 @@ mod tests {
 71
 8
 76";
-        let tree = Node::new_from_weights(&parse_input(input));
+        let tree = Node::new_from_weights(&parse_input(input).unwrap());
 
         assert_eq!(tree.min_depth(), 2);
         assert_eq!(tree.max_depth(), 5);
@@ -148,7 +167,7 @@ mod tests {
 442
 327
 930";
-        let tree = Node::new_from_weights(&parse_input(input));
+        let tree = Node::new_from_weights(&parse_input(input).unwrap());
 
         assert_eq!(tree.min_depth(), 3);
         assert_eq!(tree.max_depth(), 6);
@@ -160,7 +179,10 @@ mod tests {
         let mut input = String::new();
         file.read_to_string(&mut input).unwrap();
 
-        let tree = Node::new_from_weights(&parse_input(&input));
+        let weights = parse_input(&input)
+            .map_err(|e| e.in_file("data/huffman.txt"))
+            .unwrap();
+        let tree = Node::new_from_weights(&weights);
 
         dbg!(tree.min_depth());
         dbg!(tree.max_depth());
@@ -1,3 +1,5 @@
+use graphs::parse::{Lines, ParseError};
+
 /// Returns an array of booleans indicating whether a given vertex
 /// is included or not in the maximum weight independent set.
 fn mwis(w: &[usize]) -> Vec<bool> {
@@ -28,8 +30,24 @@ fn mwis(w: &[usize]) -> Vec<bool> {
     res
 }
 
-fn parse_input(input: &str) -> Vec<usize> {
-    input.lines().skip(1).map(|x| x.parse().unwrap()).collect()
+fn parse_input(input: &str) -> Result<Vec<usize>, ParseError> {
+    let mut lines = Lines::new(input);
+    let mut header = lines.expect_line("the number of vertices")?;
+    let n = header.next::<usize>("the number of vertices")?;
+    header.expect_end()?;
+
+    let weights = (0..n)
+        .map(|_| {
+            let mut line = lines.expect_line("a vertex weight")?;
+            let weight = line.next::<usize>("a vertex weight")?;
+            line.expect_end()?;
+
+            Ok(weight)
+        })
+        .collect::<Result<Vec<usize>, ParseError>>()?;
+    lines.expect_end()?;
+
+    Ok(weights)
 }
 
 #[cfg(test)]
@@ -42,6 +60,12 @@ mod tests {
     fn small() {
         let weights = [1, 4, 5, 4];
         assert_eq!(mwis(&weights), vec![false, true, false, true]);
+
+        assert_eq!(parse_input("4\n1\n4\n5\n4").unwrap(), weights);
+        assert_eq!(
+            parse_input("4\n1\n4\n5").unwrap_err(),
+            ParseError::new(5, "a vertex weight", None)
+        );
     }
 
     #[test]
@@ -53,7 +77,9 @@ mod tests {
             .read_to_string(&mut input)
             .unwrap();
 
-        let weights = parse_input(&input);
+        let weights = parse_input(&input)
+            .map_err(|e| e.in_file("data/mwis.txt"))
+            .unwrap();
         let mwis = mwis(&weights);
 
         let sol = [1, 2, 3, 4, 17, 117, 517, 997]
@@ -6,12 +6,15 @@
 ///
 /// - 0: We just eliminate duplicate nodes.
 /// - 1: We create all possible values at distance 1 using bit operations
-///      and if they are in the graph we add them to the same cluster.
+///   and if they are in the graph we add them to the same cluster.
 /// - 2: We create all possible values at distance 2 using bit operations
-///      and if they are in the graph we add them to the same cluster.
+///   and if they are in the graph we add them to the same cluster.
 use std::collections::HashMap;
 
-use super::union_find::UnionFind;
+use graphs::{
+    parse::{Lines, ParseError},
+    union_find::UnionFind,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 struct Graph {
@@ -20,30 +23,36 @@ struct Graph {
 }
 
 impl Graph {
-    fn new_from_str(string: &str) -> Self {
-        let mut lines = string.lines();
+    fn new_from_str(string: &str) -> Result<Self, ParseError> {
+        let mut lines = Lines::new(string);
 
-        let mut first_line_split = lines.next().unwrap().split_whitespace();
-        let _ = first_line_split.next().unwrap();
-        let bits = first_line_split.next().unwrap().parse().unwrap();
+        let mut first_line = lines.expect_line("the number of nodes and bits")?;
+        let n = first_line.next::<usize>("the number of nodes")?;
+        let bits = first_line.next::<usize>("the number of bits")?;
+        first_line.expect_end()?;
 
         let mut nodes: HashMap<usize, usize> = HashMap::new();
 
-        lines.for_each(|line| {
+        for _ in 0..n {
+            let mut line = lines.expect_line("the bits of a node")?;
             let mut node = 0;
-            for bit in line.split_whitespace() {
+            for _ in 0..bits {
                 node <<= 1;
-                if bit == "1" {
-                    node += 1;
+                match line.next_str("a bit")? {
+                    "0" => {}
+                    "1" => node += 1,
+                    bit => return Err(line.error("a bit", Some(bit))),
                 }
             }
+            line.expect_end()?;
 
             if !nodes.contains_key(&node) {
                 nodes.insert(node, nodes.len());
             }
-        });
+        }
+        lines.expect_end()?;
 
-        Self { bits, nodes }
+        Ok(Self { bits, nodes })
     }
 }
 
@@ -102,7 +111,8 @@ mod tests {
 0 1 1 0
 0 1 1 1
 1 0 0 0",
-        );
+        )
+        .unwrap();
 
         assert_eq!(
             &graph.nodes,
@@ -117,6 +127,11 @@ mod tests {
                 (8, 7),
             ])
         );
+
+        assert_eq!(
+            Graph::new_from_str("3 2\n0 1\n1 1").unwrap_err(),
+            ParseError::new(4, "the bits of a node", None)
+        );
     }
 
     #[test]
@@ -128,7 +143,8 @@ mod tests {
 0 0 0 0 1
 1 1 1 0 0
 0 0 0 1 0",
-        );
+        )
+        .unwrap();
 
         assert_eq!(solve(&graph), 2);
     }
@@ -148,7 +164,8 @@ mod tests {
 0 0 0 0 0 0 0 0 0 0 0 0 0 1 1 1 1 1 1 1 1 0 0 0
 0 0 0 0 0 0 0 0 0 0 0 0 0 1 1 1 1 1 1 1 1 1 0 0
 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 1 1 1 1 1 1 0 0 0",
-        );
+        )
+        .unwrap();
 
         assert_eq!(solve(&graph), 6);
     }
@@ -159,7 +176,9 @@ mod tests {
         let mut buffer = String::new();
         file.read_to_string(&mut buffer).unwrap();
 
-        let graph = Graph::new_from_str(&buffer);
+        let graph = Graph::new_from_str(&buffer)
+            .map_err(|e| e.in_file("data/clustering_big.txt"))
+            .unwrap();
         dbg!(solve(&graph));
     }
 }
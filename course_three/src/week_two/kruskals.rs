@@ -1,11 +1,15 @@
 use std::collections::HashMap;
 
-use super::union_find::UnionFind;
+use graphs::{
+    parse::{Lines, ParseError},
+    union_find::UnionFind,
+};
 
 #[derive(Debug, Clone)]
 struct Graph {
     n: usize,
-    edge_list: Vec<Edge>,
+    edge_list: Vec<Edge>,          // sorted by cost
+    adj: Vec<Vec<(usize, usize)>>, // the (neighbour, cost) pairs of every vertex
 }
 
 #[derive(Debug, Clone)]
@@ -15,43 +19,58 @@ struct Edge {
     c: usize,
 }
 
-fn parse_input(string: &str) -> Graph {
-    let mut lines = string.lines();
-    let n = lines.next().unwrap().parse::<usize>().unwrap();
+fn parse_input(string: &str) -> Result<Graph, ParseError> {
+    let mut lines = Lines::new(string);
+    let mut first_line = lines.expect_line("the number of nodes")?;
+    let n = first_line.next::<usize>("the number of nodes")?;
+    first_line.expect_end()?;
 
     let mut edge_list = lines
-        .map(|line| {
-            let mut split = line.split_whitespace();
-            let a = split.next().unwrap().parse::<usize>().unwrap() - 1;
-            let b = split.next().unwrap().parse::<usize>().unwrap() - 1;
-            let c = split.next().unwrap().parse::<usize>().unwrap();
-
-            Edge { a: usize::min(a, b), b: usize::max(a, b), c }
+        .map(|mut line| {
+            let a = line.vertex_below(n)?;
+            let b = line.vertex_below(n)?;
+            let c = line.next::<usize>("an edge cost")?;
+            line.expect_end()?;
+
+            Ok(Edge {
+                a: usize::min(a, b),
+                b: usize::max(a, b),
+                c,
+            })
         })
-        .collect::<Vec<Edge>>();
+        .collect::<Result<Vec<Edge>, ParseError>>()?;
 
     edge_list.sort_by_key(|x| x.c);
 
-    Graph { n, edge_list }
+    let mut adj = vec![Vec::new(); n];
+    for &Edge { a, b, c } in &edge_list {
+        adj[a].push((b, c));
+        adj[b].push((a, c));
+    }
+
+    Ok(Graph { n, edge_list, adj })
 }
 
-fn kruskals_algorithm(graph: &Graph) -> usize {
-    let mut uf = UnionFind::new(graph.n);
+impl graphs::Graph for Graph {
+    type Weight = usize;
 
-    let mut iter = graph.edge_list.iter();
-    let mut cost = 0;
-    while let Some(&Edge { a, b, c }) = iter.next() {
-        if uf.find(a) == uf.find(b) {
-            // cycle
-            continue;
-        }
+    fn vertex_count(&self) -> usize {
+        self.n
+    }
 
-        cost += c;
-        uf.union(a, b);
-        dbg!((a, b, c));
+    fn edge_count(&self) -> usize {
+        2 * self.edge_list.len()
     }
 
-    cost
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.adj[v].iter().copied()
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (usize, usize, usize)> {
+        self.edge_list
+            .iter()
+            .flat_map(|&Edge { a, b, c }| [(a, b, c), (b, a, c)])
+    }
 }
 
 /// Returns the mapping of every vertex and it's corresponding cluster
@@ -72,7 +91,7 @@ fn clustering(graph: &Graph, k: usize) -> Vec<usize> {
     uf.get_leaders()
 }
 
-fn calculate_spacing(graph: &Graph, leaders: &Vec<usize>) -> usize {
+fn calculate_spacing(graph: &Graph, leaders: &[usize]) -> usize {
     let lookup: HashMap<(usize, usize), usize> =
         graph.edge_list.iter().map(|e| ((e.a, e.b), e.c)).collect();
 
@@ -93,6 +112,7 @@ fn calculate_spacing(graph: &Graph, leaders: &Vec<usize>) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use graphs::mst::kruskals_algorithm;
     use std::{fs::File, io::Read};
 
     #[test]
@@ -103,9 +123,13 @@ mod tests {
 1 4 3
 2 4 2
 3 4 5";
-        let graph = parse_input(small);
+        let graph = parse_input(small).unwrap();
 
         assert_eq!(kruskals_algorithm(&graph), 7);
+        assert_eq!(
+            graphs::Graph::weighted_neighbours(&graph, 3).collect::<Vec<_>>(),
+            vec![(1, 2), (0, 3), (2, 5)]
+        );
     }
 
     #[test]
@@ -114,7 +138,9 @@ mod tests {
         let mut buffer = String::new();
         file.read_to_string(&mut buffer).unwrap();
 
-        let graph = parse_input(&buffer);
+        let graph = parse_input(&buffer)
+            .map_err(|e| e.in_file("data/clustering.txt"))
+            .unwrap();
         let leaders = clustering(&graph, 4);
 
         let spacing = calculate_spacing(&graph, &leaders);
@@ -1,3 +1,2 @@
 mod kruskals;
-mod union_find;
 mod hamming_clustering;
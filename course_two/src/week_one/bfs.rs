@@ -1,69 +1,8 @@
-use std::collections::{HashMap, HashSet, VecDeque};
-
-use super::graph::Graph;
-
-fn shortest_paths(graph: &Graph, start: usize) -> HashMap<usize, usize> {
-    let mut distances = HashMap::new();
-    distances.insert(start, 0);
-    let mut visited = HashSet::new();
-    visited.insert(start);
-
-    let mut queue = VecDeque::from([start]);
-    while queue.len() != 0 {
-        let curr = queue.pop_front().unwrap();
-
-        for &neighbour in &graph.adj[&curr] {
-            if !visited.contains(&neighbour) {
-                visited.insert(neighbour);
-                queue.push_back(neighbour);
-
-                distances.insert(neighbour, distances[&curr] + 1);
-            }
-        }
-    }
-
-    dbg!(visited);
-
-    distances
-}
-
-fn connected_components(graph: &Graph) -> Vec<Vec<usize>> {
-    let mut components = Vec::new();
-    let mut visited = HashSet::new();
-
-    fn bfs(graph: &Graph, start: usize, component: &mut Vec<usize>, visited: &mut HashSet<usize>) {
-        let mut queue = VecDeque::from([start]);
-        visited.insert(start);
-        component.push(start);
-
-        while queue.len() != 0 {
-            let curr = queue.pop_front().unwrap();
-
-            for &neighbour in &graph.adj[&curr] {
-                if !visited.contains(&neighbour) {
-                    visited.insert(neighbour);
-                    queue.push_back(neighbour);
-
-                    component.push(neighbour);
-                }
-            }
-        }
-    }
-
-    for &vertex in graph.adj.keys() {
-        if !visited.contains(&vertex) {
-            let mut component = Vec::new();
-            bfs(graph, vertex, &mut component, &mut visited);
-            components.push(component);
-        }
-    }
-
-    components
-}
-
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use graphs::bfs::{connected_components, is_bipartite, shortest_paths, OddCycle};
+
+    use crate::week_one::graph::Graph;
 
     const E1: &str = r"1 2
 1 3
@@ -76,9 +15,11 @@ mod tests {
 
     #[test]
     fn shortest_paths_test() {
-        let graph = Graph::new_from_edge_list_undirected(E1);
-        dbg!(&graph);
-        dbg!(shortest_paths(&graph, 0));
+        let graph = Graph::new_from_edge_list_undirected(E1).unwrap();
+        let bfs = shortest_paths(&graph, 0);
+
+        assert_eq!(bfs.distance(5), Some(3));
+        assert_eq!(bfs.path_to(5).unwrap().len(), 4);
     }
 
     const E2: &str = r"1 3
@@ -93,9 +34,49 @@ mod tests {
 
     #[test]
     fn undirected_connectivity_test() {
-        let graph = Graph::new_from_edge_list_undirected(E2);
+        let graph = Graph::new_from_edge_list_undirected(E2).unwrap();
         dbg!(&graph);
 
         dbg!(connected_components(&graph));
     }
+
+    #[test]
+    fn labeled_connectivity_test() {
+        let graph = Graph::new_labeled_from_edge_list_undirected(
+            r"alpha beta
+beta gamma
+delta epsilon",
+        )
+        .unwrap();
+
+        let components = connected_components(&graph)
+            .into_iter()
+            .map(|c| graph.labels(c))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            components,
+            vec![vec!["alpha", "beta", "gamma"], vec!["delta", "epsilon"]]
+        );
+    }
+
+    #[test]
+    fn conflict_test() {
+        // two talks conflict when someone wants to attend both
+        let graph = Graph::new_labeled_from_edge_list_undirected(
+            r"graphs heaps
+heaps hashing
+hashing graphs
+sorting hashing",
+        )
+        .unwrap();
+
+        let OddCycle(cycle) = is_bipartite(&graph).unwrap_err();
+        let mut cycle = graph.labels(cycle);
+        cycle.sort_unstable();
+        assert_eq!(cycle, vec!["graphs", "hashing", "heaps"]);
+
+        let graph = Graph::new_from_edge_list_undirected("1 2\n2 3\n3 4\n4 1").unwrap();
+        assert_eq!(is_bipartite(&graph), Ok(vec![false, true, false, true]));
+    }
 }
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use graphs::biconnected::biconnectivity;
+
+    use crate::week_one::graph::Graph;
+
+    #[test]
+    fn single_points_of_failure_test() {
+        // two rings of routers joined through gw-a and gw-b
+        let graph = Graph::new_labeled_from_edge_list_undirected(
+            r"r1 r2
+r2 r3
+r3 r1
+r3 gw-a
+gw-a gw-b
+gw-b r4
+r4 r5
+r5 gw-b",
+        )
+        .unwrap();
+
+        let result = biconnectivity(&graph);
+
+        let mut bridges = result
+            .bridges
+            .iter()
+            .map(|&(u, v)| {
+                let mut bridge = [graph.label(u), graph.label(v)];
+                bridge.sort_unstable();
+                bridge
+            })
+            .collect::<Vec<_>>();
+        bridges.sort_unstable();
+        assert_eq!(bridges, vec![["gw-a", "gw-b"], ["gw-a", "r3"]]);
+
+        let mut points = graph.labels(result.articulation_points);
+        points.sort_unstable();
+        assert_eq!(points, vec!["gw-a", "gw-b", "r3"]);
+
+        assert_eq!(result.two_edge_connected.len(), 3);
+        assert_eq!(result.biconnected.len(), 4);
+    }
+}
@@ -1,54 +1,8 @@
-use std::collections::{HashMap, HashSet};
-
-use super::graph::Graph;
-
-fn topological_sort(graph: &Graph) -> HashMap<usize, usize> {
-    let mut current_label = graph.adj.keys().count();
-    let mut ordering = HashMap::new();
-    let mut visited = HashSet::new();
-
-    fn dfs(
-        graph: &Graph,
-        vertex: usize,
-        visited: &mut HashSet<usize>,
-        current_label: &mut usize,
-        ordering: &mut HashMap<usize, usize>,
-    ) {
-        visited.insert(vertex);
-        for &neighbour in &graph.adj[&vertex] {
-            if !visited.contains(&neighbour) {
-                dfs(
-                    &graph,
-                    neighbour,
-                    visited,
-                    current_label,
-                    ordering,
-                );
-            }
-        }
-
-        *current_label -= 1;
-        ordering.insert(vertex, *current_label);
-    }
-
-    for &vertex in graph.adj.keys() {
-        if !visited.contains(&vertex) {
-            dfs(
-                &graph,
-                vertex,
-                &mut visited,
-                &mut current_label,
-                &mut ordering,
-            );
-        }
-    }
-
-    ordering
-}
-
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use graphs::dfs::{kahn, topological_sort, Cycle};
+
+    use crate::week_one::graph::Graph;
 
     const E1: &str = r"1 2
 1 3
@@ -57,10 +11,30 @@ mod tests {
 
     #[test]
     fn topological_sort_test() {
-        let graph = Graph::new_from_edge_list(E1);
+        let graph = Graph::new_from_edge_list(E1).unwrap();
 
-        let ordering = topological_sort(&graph);
-        assert_eq!(ordering[&0], 0);
-        assert_eq!(ordering[&3], 3);
+        let order = topological_sort(&graph).unwrap();
+        assert_eq!(order[0], 0);
+        assert_eq!(order[3], 3);
+    }
+
+    #[test]
+    fn build_order_test() {
+        // every line is a target and one of its dependencies
+        let graph = Graph::new_labeled_from_edge_list(
+            r"core alloc
+alloc std
+core std
+graphs std
+graphs core",
+        )
+        .unwrap();
+        let reversed = graphs::AdjacencyList::reversed(&graph);
+
+        let order = kahn(&reversed).unwrap();
+        assert_eq!(graph.labels(order), vec!["std", "alloc", "core", "graphs"]);
+
+        let cyclic = Graph::new_from_edge_list("1 2\n2 3\n3 1").unwrap();
+        assert_eq!(topological_sort(&cyclic), Err(Cycle(vec![0, 1, 2])));
     }
 }
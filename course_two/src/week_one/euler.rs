@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use graphs::euler::{eulerian_circuit, eulerian_path, NotEulerian};
+
+    use crate::week_one::graph::Graph;
+
+    #[test]
+    fn route_inspection_test() {
+        // streets between intersections, every one has to be swept once
+        let streets = r"1 2
+2 3
+3 4
+4 1
+1 3";
+        let graph = Graph::new_from_edge_list_undirected(streets).unwrap();
+
+        let route = eulerian_path(&graph, false).unwrap();
+        assert_eq!(route.len(), 6);
+        assert!(route[0] == 0 && route[5] == 2 || route[0] == 2 && route[5] == 0);
+
+        assert_eq!(
+            eulerian_circuit(&graph, false).unwrap_err().to_string(),
+            "too many vertices of odd degree: 1 (3), 3 (3)"
+        );
+
+        // walking 3 -> 1 as well closes the route
+        let graph = Graph::new_from_edge_list_undirected(&format!("{}\n3 1", streets)).unwrap();
+        let route = eulerian_circuit(&graph, false).unwrap();
+        assert_eq!(route.len(), 7);
+        assert_eq!(route.first(), route.last());
+    }
+
+    #[test]
+    fn one_way_test() {
+        let graph = Graph::new_from_edge_list("1 2\n2 3\n3 1\n1 3").unwrap();
+
+        assert_eq!(eulerian_path(&graph, true), Ok(vec![0, 1, 2, 0, 2]));
+        assert_eq!(
+            eulerian_circuit(&graph, true),
+            Err(NotEulerian::Unbalanced(vec![(0, 1), (2, -1)]))
+        );
+    }
+}
@@ -1,64 +1,88 @@
 use std::collections::HashMap;
 
+use graphs::{
+    parse::{Indexed, Lines, ParseError, Vertices},
+    LabeledGraph, SymbolTable,
+};
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Graph {
     pub adj: HashMap<usize, Vec<usize>>,
+    pub n: usize,
 }
 
 impl Graph {
-    pub fn new_from_edge_list(edge_list: &str) -> Self {
-        let mut adj = HashMap::new();
+    pub fn new_from_edge_list(edge_list: &str) -> Result<Self, ParseError> {
+        Self::parse_edge_list(edge_list, false, &mut Indexed)
+    }
 
-        edge_list.lines().for_each(|l| {
-            let mut split = l.split_whitespace();
-            let a = split
-                .next()
-                .expect("An edge should contain two vertices")
-                .parse::<usize>()
-                .unwrap()
-                - 1;
-            let b = split
-                .next()
-                .expect("An edge should contain two vertices")
-                .parse::<usize>()
-                .unwrap()
-                - 1;
+    pub fn new_from_edge_list_undirected(edge_list: &str) -> Result<Self, ParseError> {
+        Self::parse_edge_list(edge_list, true, &mut Indexed)
+    }
 
-            let ae = adj.entry(a).or_insert(Vec::new());
-            ae.push(b);
+    /// Same as `new_from_edge_list` but the vertices can be any labels.
+    pub fn new_labeled_from_edge_list(edge_list: &str) -> Result<LabeledGraph<Self>, ParseError> {
+        let mut symbols = SymbolTable::new();
+        let graph = Self::parse_edge_list(edge_list, false, &mut symbols)?;
 
-            adj.entry(b).or_insert(Vec::new());
-        });
+        Ok(LabeledGraph::new(graph, symbols))
+    }
+
+    pub fn new_labeled_from_edge_list_undirected(
+        edge_list: &str,
+    ) -> Result<LabeledGraph<Self>, ParseError> {
+        let mut symbols = SymbolTable::new();
+        let graph = Self::parse_edge_list(edge_list, true, &mut symbols)?;
 
-        Self { adj }
+        Ok(LabeledGraph::new(graph, symbols))
     }
 
-    pub fn new_from_edge_list_undirected(edge_list: &str) -> Self {
+    fn parse_edge_list<V: Vertices>(
+        edge_list: &str,
+        undirected: bool,
+        vertices: &mut V,
+    ) -> Result<Self, ParseError> {
         let mut adj = HashMap::new();
+        let mut n = 0;
 
-        edge_list.lines().for_each(|l| {
-            let mut split = l.split_whitespace();
-            let a = split
-                .next()
-                .expect("An edge should contain two vertices")
-                .parse::<usize>()
-                .unwrap()
-                - 1;
-            let b = split
-                .next()
-                .expect("An edge should contain two vertices")
-                .parse::<usize>()
-                .unwrap()
-                - 1;
+        for mut l in Lines::new(edge_list) {
+            let a = l.vertex_with(vertices)?;
+            let b = l.vertex_with(vertices)?;
+            l.expect_end()?;
+            n = usize::max(n, usize::max(a, b) + 1);
 
             let ae = adj.entry(a).or_insert(Vec::new());
             ae.push(b);
 
             let be = adj.entry(b).or_insert(Vec::new());
-            be.push(a);
-        });
+            if undirected {
+                be.push(a);
+            }
+        }
+
+        Ok(Self { adj, n })
+    }
+}
+
+impl graphs::Graph for Graph {
+    type Weight = usize;
+
+    // the vertices are the keys of `adj`, which can have gaps that are then
+    // vertices without edges, so `n` is one more than the largest of them
+    fn vertex_count(&self) -> usize {
+        self.n
+    }
+
+    fn edge_count(&self) -> usize {
+        self.adj.values().map(|l| l.len()).sum()
+    }
+
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.adj.get(&v).into_iter().flatten().map(|&w| (w, 1))
+    }
 
-        Self { adj }
+    fn neighbours(&self, v: usize) -> impl Iterator<Item = usize> {
+        self.adj.get(&v).into_iter().flatten().copied()
     }
 }
 
@@ -70,11 +94,12 @@ mod tests {
     fn new_from_edge_list_test() {
         let edge_list = r"1 2
 2 1";
-        assert_eq!(Graph::new_from_edge_list(edge_list), Graph {
+        assert_eq!(Graph::new_from_edge_list(edge_list).unwrap(), Graph {
             adj: HashMap::from([
                 (0, vec![1]),
                 (1, vec![0]),
-            ])
+            ]),
+            n: 2,
         });
     }
 
@@ -82,11 +107,58 @@ mod tests {
     fn new_from_edge_list_undirected_test() {
         let edge_list = r"1 2";
 
-        assert_eq!(Graph::new_from_edge_list_undirected(edge_list), Graph {
+        assert_eq!(Graph::new_from_edge_list_undirected(edge_list).unwrap(), Graph {
             adj: HashMap::from([
                 (0, vec![1]),
                 (1, vec![0]),
-            ])
+            ]),
+            n: 2,
         });
     }
+
+    #[test]
+    fn malformed_edge_list_test() {
+        let edge_list = r"1 2
+2 x";
+
+        assert_eq!(
+            Graph::new_from_edge_list(edge_list),
+            Err(ParseError::new(2, "a 1-indexed vertex", Some("x")))
+        );
+    }
+
+    #[test]
+    fn gap_test() {
+        // no edges touch 2, 3 and 4
+        let graph = Graph::new_from_edge_list("1 5").unwrap();
+
+        assert_eq!(graphs::Graph::vertex_count(&graph), 5);
+        assert_eq!(graphs::Graph::neighbours(&graph, 1).count(), 0);
+        assert_eq!(
+            graphs::bfs::connected_components(&graph),
+            vec![vec![0, 4], vec![1], vec![2], vec![3]]
+        );
+        assert_eq!(
+            graphs::dfs::topological_sort(&graph).unwrap(),
+            vec![3, 2, 1, 0, 4]
+        );
+        assert_eq!(graphs::scc::kosaraju(&graph).len(), 5);
+    }
+
+    #[test]
+    fn new_labeled_from_edge_list_test() {
+        let edge_list = r"web-1 db
+web-2 db
+db backup";
+
+        let graph = Graph::new_labeled_from_edge_list(edge_list).unwrap();
+        assert_eq!(graph.labels(0..4), vec!["web-1", "db", "web-2", "backup"]);
+        assert_eq!(graph.graph.adj[&graph.index("db").unwrap()], vec![3]);
+
+        let undirected = Graph::new_labeled_from_edge_list_undirected(edge_list).unwrap();
+        assert_eq!(
+            undirected.labels(undirected.graph.adj[&1].iter().copied()),
+            vec!["web-1", "web-2", "backup"]
+        );
+    }
 }
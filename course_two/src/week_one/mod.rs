@@ -1,4 +1,6 @@
 mod bfs;
+mod biconnected;
 mod dfs;
+mod euler;
 mod graph;
 mod kosaraju;
@@ -1,4 +1,7 @@
-use std::collections::BTreeSet;
+use graphs::{
+    parse::{Indexed, Lines, ParseError, Vertices},
+    LabeledGraph, SymbolTable,
+};
 
 #[derive(Debug)]
 struct Graph {
@@ -7,70 +10,80 @@ struct Graph {
     m: usize,
 }
 
-fn read_graph(data: &str) -> Graph {
-    let mut n = 0;
-    let mut m = 0;
-    data.lines().for_each(|x| {
-        let mut iter = x.split_whitespace();
-        let index = iter.next().unwrap().parse::<usize>().unwrap() - 1;
-        n = n.max(index);
-
-        iter.for_each(|pair| {
-            let vertex = pair.split(',').next().unwrap().parse::<usize>().unwrap() - 1;
-            n = n.max(vertex);
-            m += 1;
-        });
-    });
-    n += 1;
+fn read_graph(data: &str) -> Result<Graph, ParseError> {
+    parse_graph(data, &mut Indexed)
+}
 
-    let mut adj = vec![Vec::new(); n];
+/// Same as `read_graph` but the vertices can be any labels without whitespace.
+fn read_labeled_graph(data: &str) -> Result<LabeledGraph<Graph>, ParseError> {
+    let mut symbols = SymbolTable::new();
+    let graph = parse_graph(data, &mut symbols)?;
 
-    data.lines().for_each(|x| {
-        let mut iter = x.split_whitespace();
-        let index = iter.next().unwrap().parse::<usize>().unwrap() - 1;
+    Ok(LabeledGraph::new(graph, symbols))
+}
 
-        adj[index].extend(
-            iter.map(|pair| {
-                let mut pair_iter = pair.split(',');
-                let vertex = pair_iter.next().unwrap().parse::<usize>().unwrap() - 1;
-                let distance = pair_iter.next().unwrap().parse::<usize>().unwrap();
+/// Every line is a vertex followed by the `vertex,distance` pairs of its edges.
+fn parse_graph<V: Vertices>(data: &str, vertices: &mut V) -> Result<Graph, ParseError> {
+    const PAIR: &str = "a `vertex,distance` pair";
 
-                (vertex, distance)
-            })
-        );
-    });
+    let mut n = 0;
+    let mut m = 0;
+    let mut rows = Vec::new();
 
-    Graph { adj, n, m }
-}
+    for mut line in Lines::new(data) {
+        let index = line.vertex_with(vertices)?;
+        n = n.max(index + 1);
 
-fn dijkstras_algorithm(graph: &Graph, start: usize) -> Vec<usize> {
-    let mut distances = vec![usize::MAX; graph.n];
-    distances[start] = 0;
+        let mut row = Vec::new();
+        while !line.is_empty() {
+            let pair = line.next_str(PAIR)?;
+            let (vertex, distance) = pair
+                .rsplit_once(',')
+                .and_then(|(v, d)| Some((vertices.vertex(v)?, d.parse::<usize>().ok()?)))
+                .ok_or_else(|| line.error(PAIR, Some(pair)))?;
 
-    let mut queue: BTreeSet<(usize, usize)> = BTreeSet::new(); // (distance, vertex)
-    queue.insert((0, start));
+            n = n.max(vertex + 1);
+            m += 1;
+            row.push((vertex, distance));
+        }
+
+        rows.push((index, row));
+    }
+
+    let mut adj = vec![Vec::new(); n];
+    for (index, row) in rows {
+        adj[index].extend(row);
+    }
 
-    while queue.len() != 0 {
-        let (_, current_vertex) = queue.pop_first().unwrap();
+    Ok(Graph { adj, n, m })
+}
 
-        for &(neighbour_vertex, neighbour_distance) in graph.adj[current_vertex].iter() {
-            if distances[current_vertex] + neighbour_distance < distances[neighbour_vertex] {
-                queue.remove(&(distances[neighbour_vertex], neighbour_vertex));
+impl graphs::Graph for Graph {
+    type Weight = usize;
 
-                distances[neighbour_vertex] = distances[current_vertex] + neighbour_distance;
+    fn vertex_count(&self) -> usize {
+        self.n
+    }
 
-                queue.insert((distances[neighbour_vertex], neighbour_vertex));
-            }
-        }
+    fn edge_count(&self) -> usize {
+        self.m
     }
 
-    distances
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.adj[v].iter().copied()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::io::Read;
 
+    use graphs::{
+        dag,
+        dijkstra::{dijkstras_algorithm, shortest_path},
+        generate,
+    };
+
     use super::*;
 
     #[test]
@@ -79,9 +92,75 @@ mod tests {
             r"1 2,1 3,4
 2 3,2 4,6
 3 4,3",
+        )
+        .unwrap();
+        let paths = dijkstras_algorithm(&graph, 0);
+
+        assert_eq!(paths.distances, vec![Some(0), Some(1), Some(3), Some(6)]);
+        assert_eq!(paths.path_to(3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(shortest_path(&graph, 0, 2).path_to(2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn generated_test() {
+        let (road, _) = generate::road(8, 8, 2);
+        let graph = read_graph(&generate::to_weighted_adjacency_list(&road)).unwrap();
+
+        assert_eq!(
+            dijkstras_algorithm(&graph, 0),
+            dijkstras_algorithm(&road, 0)
+        );
+    }
+
+    #[test]
+    fn labeled_test() {
+        let graph = read_labeled_graph(
+            r"home shop,1 school,4
+shop school,2 work,6
+school work,3",
+        )
+        .unwrap();
+
+        let paths = dijkstras_algorithm(&graph, graph.index("home").unwrap());
+        let distances = graph.by_label(paths.reached());
+
+        assert_eq!(distances["school"], 3);
+        assert_eq!(distances["work"], 6);
+    }
+
+    #[test]
+    fn dag_test() {
+        // tasks between milestones, with how many days each takes
+        let graph = read_graph(
+            r"1 2,3 3,2
+2 4,4
+3 4,1 5,6
+4
+5",
+        )
+        .unwrap();
+
+        let longest = dag::longest_paths(&graph, 0).unwrap();
+        assert_eq!(longest.path_to(3), Some(vec![0, 1, 3]));
+
+        let schedule = dag::critical_path(&graph).unwrap();
+        assert_eq!(schedule.length, 8);
+        assert_eq!(schedule.path, vec![0, 2, 4]);
+        assert_eq!(schedule.slack(3), 1);
+
+        let cyclic = read_graph("1 2,1\n2 3,1\n3 1,1").unwrap();
+        assert_eq!(
+            dag::critical_path(&cyclic).unwrap_err().to_string(),
+            "the graph has a cycle: 1 -> 2 -> 3 -> 1"
+        );
+    }
+
+    #[test]
+    fn malformed_test() {
+        assert_eq!(
+            read_graph("1 2,1\n2 3;2").unwrap_err(),
+            ParseError::new(2, "a `vertex,distance` pair", Some("3;2"))
         );
-        dbg!(&graph);
-        dbg!(dijkstras_algorithm(&graph, 0));
     }
 
     #[test]
@@ -90,14 +169,16 @@ mod tests {
         let mut buffer = String::new();
         file.read_to_string(&mut buffer).unwrap();
 
-        let graph = read_graph(&buffer);
+        let graph = read_graph(&buffer)
+            .map_err(|e| e.in_file("./data/dijkstra_data.txt"))
+            .unwrap();
         dbg!(&graph);
 
-        let distances = dijkstras_algorithm(&graph, 0);
-        dbg!(&distances);
+        // unreachable vertices are reported as 1000000
+        let paths = dijkstras_algorithm(&graph, 0);
         let res = [7, 37, 59, 82, 99, 115, 133, 165, 188, 197]
             .into_iter()
-            .map(|x| distances[x - 1])
+            .map(|x| paths.distance(x - 1).unwrap_or(1000000))
             .collect::<Vec<usize>>();
 
         dbg!(&res);
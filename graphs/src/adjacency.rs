@@ -0,0 +1,66 @@
+use crate::Graph;
+
+/// A weighted adjacency list, `adj[u]` holds the (head, weight) pairs of
+/// the edges leaving `u`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjacencyList<W = usize> {
+    pub adj: Vec<Vec<(usize, W)>>,
+}
+
+impl<W: Copy> AdjacencyList<W> {
+    pub fn new(n: usize) -> Self {
+        Self {
+            adj: vec![Vec::new(); n],
+        }
+    }
+
+    pub fn new_from_edges(n: usize, edges: impl IntoIterator<Item = (usize, usize, W)>) -> Self {
+        let mut graph = Self::new(n);
+        for (u, v, c) in edges {
+            graph.adj[u].push((v, c));
+        }
+
+        graph
+    }
+
+    /// The same graph with the direction of every edge flipped.
+    pub fn reversed<G: Graph<Weight = W>>(graph: &G) -> Self {
+        Self::new_from_edges(
+            graph.vertex_count(),
+            graph.edges().map(|(u, v, c)| (v, u, c)),
+        )
+    }
+}
+
+impl<W: Copy> Graph for AdjacencyList<W> {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.adj.len()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.adj.iter().map(|l| l.len()).sum()
+    }
+
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, W)> {
+        self.adj[v].iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reversed_test() {
+        let graph = AdjacencyList::new_from_edges(3, [(0, 1, 5), (0, 2, 3), (2, 1, 1)]);
+        let reverse = AdjacencyList::reversed(&graph);
+
+        assert_eq!(
+            reverse.adj,
+            vec![vec![], vec![(0, 5), (2, 1)], vec![(0, 3)]]
+        );
+        assert_eq!(reverse.edge_count(), graph.edge_count());
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{CsrGraph, Graph, Paths};
+
+/// Follows the edges of `graph` as they are, so it works on directed and
+/// undirected graphs alike.
+pub fn shortest_paths<G: Graph>(graph: &G, start: usize) -> Paths {
+    search(graph, &[start], None, |_| 1)
+}
+
+/// Same as `shortest_paths` but stops as soon as `target` is reached, so
+/// only the vertices found by then have a distance.
+pub fn shortest_path<G: Graph>(graph: &G, start: usize, target: usize) -> Paths {
+    search(graph, &[start], Some(target), |_| 1)
+}
+
+/// Searches from all of `starts` at once, so every vertex gets the
+/// distance to its nearest start and `Paths::source` tells which one it was.
+pub fn multi_source<G: Graph>(graph: &G, starts: &[usize]) -> Paths {
+    search(graph, starts, None, |_| 1)
+}
+
+/// Shortest paths on a graph whose edges all weigh 0 or 1.
+pub fn zero_one<G: Graph<Weight = usize>>(graph: &G, start: usize) -> Paths {
+    search(graph, &[start], None, |c| {
+        assert!(c <= 1, "0-1 BFS on an edge of weight {}", c);
+        c
+    })
+}
+
+/// Same as `shortest_paths` but every level is found either top-down, from
+/// the edges leaving the frontier, or bottom-up, from the edges into every
+/// unvisited vertex until one comes from the frontier, whichever looks at
+/// fewer edges. `reverse` is `graph.transposed()`, or `graph` itself when it
+/// is undirected.
+///
+/// The parents may differ from those of `shortest_paths` but the distances
+/// are the same.
+pub fn direction_optimizing<W: Copy>(
+    graph: &CsrGraph<W>,
+    reverse: &CsrGraph<W>,
+    start: usize,
+) -> Paths {
+    // the switching thresholds from Beamer, Asanović and Patterson
+    const ALPHA: usize = 14;
+    const BETA: usize = 24;
+
+    let n = graph.vertex_count();
+    let mut bfs = Paths::new(n, &[start]);
+
+    let mut frontier = vec![start];
+    let mut in_frontier = vec![false; n];
+    // edges leaving vertices that haven't been visited yet
+    let mut unexplored = graph.edge_count() - graph.degree(start);
+    let mut bottom_up = false;
+    let mut distance = 0;
+
+    while !frontier.is_empty() {
+        distance += 1;
+
+        let frontier_edges = frontier.iter().map(|&u| graph.degree(u)).sum::<usize>();
+        if !bottom_up && frontier_edges > unexplored / ALPHA {
+            bottom_up = true;
+        } else if bottom_up && frontier.len() < n / BETA {
+            bottom_up = false;
+        }
+
+        let mut next = Vec::new();
+        if bottom_up {
+            for &u in frontier.iter() {
+                in_frontier[u] = true;
+            }
+            for v in 0..n {
+                if bfs.distances[v].is_some() {
+                    continue;
+                }
+                if let Some(u) = reverse.neighbours(v).find(|&u| in_frontier[u]) {
+                    bfs.distances[v] = Some(distance);
+                    bfs.parents[v] = Some(u);
+                    next.push(v);
+                }
+            }
+            for &u in frontier.iter() {
+                in_frontier[u] = false;
+            }
+        } else {
+            for &u in frontier.iter() {
+                for v in graph.neighbours(u) {
+                    if bfs.distances[v].is_none() {
+                        bfs.distances[v] = Some(distance);
+                        bfs.parents[v] = Some(u);
+                        next.push(v);
+                    }
+                }
+            }
+        }
+
+        unexplored -= next.iter().map(|&v| graph.degree(v)).sum::<usize>();
+        frontier = next;
+    }
+
+    bfs
+}
+
+/// The queue loop every search shares. Edges weighing 0 go to the front of
+/// the queue and edges weighing 1 to the back, so vertices leave it in the
+/// order of their distance and the first time a vertex leaves it is final.
+fn search<G, F>(graph: &G, starts: &[usize], target: Option<usize>, weight: F) -> Paths
+where
+    G: Graph,
+    F: Fn(G::Weight) -> usize,
+{
+    let n = graph.vertex_count();
+    let mut bfs = Paths::new(n, starts);
+
+    let mut queue = starts.iter().copied().collect::<VecDeque<_>>();
+    while let Some(curr) = queue.pop_front() {
+        if target == Some(curr) {
+            break;
+        }
+
+        let distance = bfs.distances[curr].unwrap();
+        for (neighbour, c) in graph.weighted_neighbours(curr) {
+            let c = weight(c);
+            if bfs.distances[neighbour].is_none_or(|d| distance + c < d) {
+                bfs.distances[neighbour] = Some(distance + c);
+                bfs.parents[neighbour] = Some(curr);
+                if c == 0 {
+                    queue.push_front(neighbour);
+                } else {
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+    }
+
+    bfs
+}
+
+pub fn connected_components<G: Graph>(graph: &G) -> Vec<Vec<usize>> {
+    let mut components = Vec::new();
+    let mut visited = HashSet::new();
+
+    fn bfs<G: Graph>(
+        graph: &G,
+        start: usize,
+        component: &mut Vec<usize>,
+        visited: &mut HashSet<usize>,
+    ) {
+        let mut queue = VecDeque::from([start]);
+        visited.insert(start);
+        component.push(start);
+
+        while let Some(curr) = queue.pop_front() {
+            for neighbour in graph.neighbours(curr) {
+                if !visited.contains(&neighbour) {
+                    visited.insert(neighbour);
+                    queue.push_back(neighbour);
+
+                    component.push(neighbour);
+                }
+            }
+        }
+    }
+
+    for vertex in graph.vertices() {
+        if !visited.contains(&vertex) {
+            let mut component = Vec::new();
+            bfs(graph, vertex, &mut component, &mut visited);
+            components.push(component);
+        }
+    }
+
+    components
+}
+
+/// An odd cycle of an undirected graph, each vertex adjacent to the next
+/// and the last to the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OddCycle(pub Vec<usize>);
+
+/// Two-colours an undirected graph so that every edge joins different
+/// colours, or finds an odd cycle proving it can't be done.
+pub fn is_bipartite<G: Graph>(graph: &G) -> Result<Vec<bool>, OddCycle> {
+    // searching from every component at once gives a shortest path tree of
+    // each, the colour of a vertex is the parity of its depth
+    let roots = connected_components(graph)
+        .iter()
+        .map(|component| component[0])
+        .collect::<Vec<_>>();
+    let bfs = multi_source(graph, &roots);
+    let depth = |v: usize| bfs.distances[v].unwrap();
+
+    for (u, v, _) in graph.edges() {
+        if depth(u) % 2 != depth(v) % 2 {
+            continue;
+        }
+
+        // the tree paths up to the lowest common ancestor close the cycle
+        let (mut a, mut b) = (vec![u], vec![v]);
+        let (mut x, mut y) = (u, v);
+        while x != y {
+            if depth(x) >= depth(y) {
+                x = bfs.parents[x].unwrap();
+                a.push(x);
+            } else {
+                y = bfs.parents[y].unwrap();
+                b.push(y);
+            }
+        }
+        b.pop();
+        a.extend(b.into_iter().rev());
+
+        return Err(OddCycle(a));
+    }
+
+    Ok(graph.vertices().map(|v| depth(v) % 2 == 1).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, AdjacencyList};
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> AdjacencyList {
+        AdjacencyList::new_from_edges(n, edges.iter().flat_map(|&(u, v)| [(u, v, 1), (v, u, 1)]))
+    }
+
+    #[test]
+    fn shortest_paths_test() {
+        let graph = undirected(
+            6,
+            &[
+                (0, 1),
+                (0, 2),
+                (1, 3),
+                (2, 3),
+                (2, 4),
+                (3, 4),
+                (3, 5),
+                (4, 5),
+            ],
+        );
+
+        let bfs = shortest_paths(&graph, 0);
+        assert_eq!(bfs.distance(0), Some(0));
+        assert_eq!(bfs.distance(3), Some(2));
+        assert_eq!(bfs.distance(5), Some(3));
+        assert_eq!(bfs.path_to(5), Some(vec![0, 1, 3, 5]));
+        assert_eq!(bfs.path_to(0), Some(vec![0]));
+
+        let early = shortest_path(&graph, 0, 2);
+        assert_eq!(early.path_to(2), Some(vec![0, 2]));
+        assert_eq!(early.distance(5), None);
+    }
+
+    #[test]
+    fn directed_test() {
+        let graph = AdjacencyList::new_from_edges(4, [(0, 1, 1), (1, 2, 1), (3, 0, 1)]);
+
+        let bfs = shortest_paths(&graph, 0);
+        assert_eq!(bfs.distances, vec![Some(0), Some(1), Some(2), None]);
+        assert_eq!(bfs.path_to(2), Some(vec![0, 1, 2]));
+        assert_eq!(bfs.path_to(3), None);
+        assert_eq!(
+            bfs.reached().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn multi_source_test() {
+        // a path 0 - 1 - 2 - 3 - 4 - 5 - 6 with sources at both ends
+        let graph = undirected(8, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 6)]);
+
+        let bfs = multi_source(&graph, &[0, 6]);
+        assert_eq!(
+            bfs.distances,
+            vec![
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(2),
+                Some(1),
+                Some(0),
+                None
+            ]
+        );
+        assert_eq!(bfs.source(1), Some(0));
+        assert_eq!(bfs.source(5), Some(6));
+        assert_eq!(bfs.source(7), None);
+    }
+
+    #[test]
+    fn zero_one_test() {
+        let graph = AdjacencyList::new_from_edges(
+            4,
+            [(0, 1, 1), (0, 2, 0), (2, 3, 0), (3, 1, 0), (1, 3, 1)],
+        );
+
+        let bfs = zero_one(&graph, 0);
+        assert_eq!(bfs.distances, vec![Some(0), Some(0), Some(0), Some(0)]);
+        assert_eq!(bfs.path_to(1), Some(vec![0, 2, 3, 1]));
+
+        // with every edge weighing 1 it is a plain BFS
+        let graph = AdjacencyList::new_from_edges(4, [(0, 1, 1), (1, 2, 1), (0, 3, 1)]);
+        assert_eq!(zero_one(&graph, 0), shortest_paths(&graph, 0));
+    }
+
+    #[test]
+    fn direction_optimizing_test() {
+        let graphs = [
+            generate::gnp(600, 0.02, true, 1),
+            generate::gnp(600, 0.002, true, 2),
+            generate::gnp(600, 0.05, false, 3),
+            generate::grid(20, 30).0,
+            generate::strongly_connected(600, 40, 0.01, 4).0,
+        ];
+
+        for graph in graphs.iter() {
+            let csr = CsrGraph::new_from_graph(graph);
+            let reverse = csr.transposed();
+
+            for start in [0, 17, 599] {
+                let bfs = direction_optimizing(&csr, &reverse, start);
+                assert_eq!(bfs.distances, shortest_paths(graph, start).distances);
+
+                // every parent is one step closer to the start
+                for (v, parent) in bfs.parents.iter().enumerate() {
+                    if let Some(u) = *parent {
+                        assert_eq!(bfs.distances[u].unwrap() + 1, bfs.distances[v].unwrap());
+                        assert!(graph.neighbours(u).any(|w| w == v));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn is_bipartite_test() {
+        let graph = undirected(6, &[(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)]);
+        let colours = is_bipartite(&graph).unwrap();
+        assert!(graph.edges().all(|(u, v, _)| colours[u] != colours[v]));
+
+        let graph = undirected(7, &[(5, 6), (0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (2, 5)]);
+        let OddCycle(cycle) = is_bipartite(&graph).unwrap_err();
+        assert_eq!(cycle.len() % 2, 1);
+        for (i, &u) in cycle.iter().enumerate() {
+            let v = cycle[(i + 1) % cycle.len()];
+            assert!(graph.neighbours(u).any(|w| w == v));
+        }
+    }
+
+    #[test]
+    fn connected_components_test() {
+        let graph = undirected(5, &[(0, 2), (2, 4), (1, 3)]);
+
+        assert_eq!(
+            connected_components(&graph),
+            vec![vec![0, 2, 4], vec![1, 3]]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Single points of failure of undirected graphs, from the lowlinks of one
+//! depth first search.
+
+use crate::{
+    dfs::{depth_first, Visitor},
+    Graph,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Biconnectivity {
+    pub bridges: Vec<(usize, usize)>, // edges whose removal disconnects the graph
+    pub articulation_points: Vec<usize>, // vertices whose removal disconnects the graph
+    pub two_edge_connected: Vec<Vec<usize>>, // the components left when every bridge is gone
+    pub biconnected: Vec<Vec<(usize, usize)>>, // the edges of every maximal biconnected subgraph
+}
+
+/// The lowlink of a vertex is the earliest discovery time reachable from
+/// its subtree through a single edge other than the one to its parent.
+struct Lowlinks {
+    discovered: Vec<usize>,
+    low: Vec<usize>,
+    parents: Vec<Option<usize>>,
+    skipped_parent: Vec<bool>, // a parallel edge to the parent is a real back edge
+    root_children: usize,
+    articulation: Vec<bool>,
+    edges: Vec<(usize, usize)>, // edges of the biconnected components being built
+    vertices: Vec<usize>,       // vertices of the 2-edge-connected components being built
+    result: Biconnectivity,
+}
+
+impl Visitor for Lowlinks {
+    fn start(&mut self, _root: usize) {
+        self.root_children = 0;
+    }
+
+    fn discover(&mut self, vertex: usize, time: usize) {
+        self.discovered[vertex] = time;
+        self.low[vertex] = time;
+        self.vertices.push(vertex);
+    }
+
+    fn tree_edge(&mut self, tail: usize, head: usize) {
+        self.parents[head] = Some(tail);
+        self.edges.push((tail, head));
+        if self.parents[tail].is_none() {
+            self.root_children += 1;
+        }
+    }
+
+    fn back_edge(&mut self, tail: usize, head: usize) {
+        if tail == head {
+            return;
+        }
+        if self.parents[tail] == Some(head) && !self.skipped_parent[tail] {
+            // the tree edge seen from the other end
+            self.skipped_parent[tail] = true;
+            return;
+        }
+
+        self.low[tail] = self.low[tail].min(self.discovered[head]);
+        self.edges.push((tail, head));
+    }
+
+    fn finish(&mut self, vertex: usize, _time: usize) {
+        let Some(parent) = self.parents[vertex] else {
+            self.articulation[vertex] = self.root_children > 1;
+            let component = self.vertices.drain(..).collect();
+            self.result.two_edge_connected.push(component);
+            return;
+        };
+
+        self.low[parent] = self.low[parent].min(self.low[vertex]);
+
+        // nothing below vertex reaches above parent, so parent separates them
+        if self.low[vertex] >= self.discovered[parent] {
+            if self.parents[parent].is_some() {
+                self.articulation[parent] = true;
+            }
+            let start = self
+                .edges
+                .iter()
+                .rposition(|&e| e == (parent, vertex))
+                .unwrap();
+            let component = self.edges.split_off(start);
+            self.result.biconnected.push(component);
+        }
+
+        // nothing below vertex reaches parent either, so the edge between them is all there is
+        if self.low[vertex] > self.discovered[parent] {
+            self.result.bridges.push((parent, vertex));
+            let start = self.vertices.iter().rposition(|&v| v == vertex).unwrap();
+            let component = self.vertices.split_off(start);
+            self.result.two_edge_connected.push(component);
+        }
+    }
+}
+
+/// `graph` should be undirected, holding both directions of every edge.
+/// Bridges and component edges are given as (tail, head) pairs the way the
+/// search went along them.
+pub fn biconnectivity<G: Graph>(graph: &G) -> Biconnectivity {
+    let n = graph.vertex_count();
+    let mut lowlinks = Lowlinks {
+        discovered: vec![0; n],
+        low: vec![0; n],
+        parents: vec![None; n],
+        skipped_parent: vec![false; n],
+        root_children: 0,
+        articulation: vec![false; n],
+        edges: Vec::new(),
+        vertices: Vec::new(),
+        result: Biconnectivity {
+            bridges: Vec::new(),
+            articulation_points: Vec::new(),
+            two_edge_connected: Vec::new(),
+            biconnected: Vec::new(),
+        },
+    };
+    depth_first(graph, graph.vertices(), &mut lowlinks);
+
+    let mut result = lowlinks.result;
+    result.articulation_points = graph
+        .vertices()
+        .filter(|&v| lowlinks.articulation[v])
+        .collect();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bfs, generate, AdjacencyList};
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> AdjacencyList {
+        AdjacencyList::new_from_edges(n, edges.iter().flat_map(|&(u, v)| [(u, v, 1), (v, u, 1)]))
+    }
+
+    fn sorted<T: Ord>(mut items: Vec<T>) -> Vec<T> {
+        items.sort_unstable();
+        items
+    }
+
+    fn normalized(edges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        sorted(edges.iter().map(|&(u, v)| (u.min(v), u.max(v))).collect())
+    }
+
+    #[test]
+    fn biconnectivity_test() {
+        // the triangle 0 1 2 hangs off 3 by the bridge 2 - 3, and the
+        // triangles 3 4 5 and 3 6 7 share 3, 8 is on its own
+        let graph = undirected(
+            9,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 0),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (5, 3),
+                (3, 6),
+                (6, 7),
+                (7, 3),
+            ],
+        );
+
+        let result = biconnectivity(&graph);
+        assert_eq!(normalized(&result.bridges), vec![(2, 3)]);
+        assert_eq!(result.articulation_points, vec![2, 3]);
+        assert_eq!(
+            sorted(result.two_edge_connected.into_iter().map(sorted).collect()),
+            vec![vec![0, 1, 2], vec![3, 4, 5, 6, 7], vec![8]]
+        );
+        assert_eq!(
+            sorted(result.biconnected.iter().map(|c| normalized(c)).collect()),
+            vec![
+                vec![(0, 1), (0, 2), (1, 2)],
+                vec![(2, 3)],
+                vec![(3, 4), (3, 5), (4, 5)],
+                vec![(3, 6), (3, 7), (6, 7)],
+            ]
+        );
+    }
+
+    #[test]
+    fn parallel_edges_test() {
+        // the doubled edge 0 - 1 is not a bridge, 1 - 2 is
+        let graph = undirected(3, &[(0, 1), (0, 1), (1, 2)]);
+
+        let result = biconnectivity(&graph);
+        assert_eq!(normalized(&result.bridges), vec![(1, 2)]);
+        assert_eq!(result.articulation_points, vec![1]);
+    }
+
+    #[test]
+    fn bridges_test() {
+        // an edge is a bridge exactly when removing it adds a component
+        let graph = generate::gnp(40, 0.06, false, 12);
+        let components = bfs::connected_components(&graph).len();
+        let bridges = normalized(&biconnectivity(&graph).bridges);
+
+        for (u, v, _) in graph.edges().filter(|&(u, v, _)| u < v) {
+            let without = AdjacencyList::new_from_edges(
+                40,
+                graph
+                    .edges()
+                    .filter(|&(a, b, _)| (a, b) != (u, v) && (a, b) != (v, u)),
+            );
+            let bridge = bfs::connected_components(&without).len() > components;
+            assert_eq!(bridge, bridges.contains(&(u, v)));
+        }
+    }
+}
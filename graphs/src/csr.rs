@@ -0,0 +1,197 @@
+use crate::{
+    parse::{Lines, ParseError},
+    Graph,
+};
+
+/// A compressed sparse row graph.
+///
+/// The heads of the edges leaving `u` are `targets[offsets[u]..offsets[u + 1]]`
+/// and their weights are at the same positions in `weights`. Unweighted graphs
+/// use `()` as the weight so that `weights` takes no space.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrGraph<W = ()> {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+}
+
+impl<W: Copy> CsrGraph<W> {
+    /// Builds the graph with a counting sort of the edges by their tail,
+    /// going over `edges` more than once instead of holding on to them.
+    pub fn new_from_edges<I>(n: usize, edges: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+        I::IntoIter: Clone,
+    {
+        let edges = edges.into_iter();
+        Self::new_from_passes(n, || edges.clone())
+    }
+
+    /// A copy of `graph` in compressed sparse rows.
+    pub fn new_from_graph<G: Graph<Weight = W>>(graph: &G) -> Self {
+        Self::new_from_passes(graph.vertex_count(), || graph.edges())
+    }
+
+    /// The graph with the direction of every edge of `graph` flipped.
+    pub fn reversed<G: Graph<Weight = W>>(graph: &G) -> Self {
+        Self::new_from_passes(graph.vertex_count(), || {
+            graph.edges().map(|(u, v, c)| (v, u, c))
+        })
+    }
+
+    /// `edges` has to give the same edges every time it is called, so that
+    /// only the final arrays are ever allocated.
+    fn new_from_passes<I>(n: usize, edges: impl Fn() -> I) -> Self
+    where
+        I: Iterator<Item = (usize, usize, W)>,
+    {
+        let mut offsets = vec![0; n + 1];
+        for (u, _, _) in edges() {
+            offsets[u + 1] += 1;
+        }
+        for u in 0..n {
+            offsets[u + 1] += offsets[u];
+        }
+
+        let mut next = offsets.clone();
+        let mut targets = vec![0; offsets[n]];
+        // every weight gets moved to its slot below, this only fills the vector
+        let mut weights = edges().map(|(_, _, c)| c).collect::<Vec<_>>();
+
+        for (u, v, c) in edges() {
+            targets[next[u]] = v;
+            weights[next[u]] = c;
+            next[u] += 1;
+        }
+
+        Self {
+            offsets,
+            targets,
+            weights,
+        }
+    }
+
+    pub fn transposed(&self) -> Self {
+        Self::reversed(self)
+    }
+
+    /// The number of edges leaving `v`.
+    pub fn degree(&self, v: usize) -> usize {
+        self.offsets[v + 1] - self.offsets[v]
+    }
+}
+
+impl CsrGraph {
+    /// Parses a list of 1-indexed `tail head` lines.
+    pub fn new_from_edge_list(edge_list: &str) -> Result<Self, ParseError> {
+        let edges = || {
+            Lines::new(edge_list).map(|mut l| {
+                let edge = (l.vertex()?, l.vertex()?, ());
+                l.expect_end()?;
+                Ok(edge)
+            })
+        };
+
+        // the lines are parsed again on every pass rather than kept, so this
+        // one finds any error before the others unwrap
+        let mut n = 0;
+        for edge in edges() {
+            let (u, v, ()) = edge?;
+            n = usize::max(n, usize::max(u, v) + 1);
+        }
+
+        Ok(Self::new_from_passes(n, || {
+            edges().map(Result::<_, ParseError>::unwrap)
+        }))
+    }
+}
+
+impl<W: Copy> Graph for CsrGraph<W> {
+    type Weight = W;
+
+    fn vertex_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, W)> {
+        let range = self.offsets[v]..self.offsets[v + 1];
+        self.targets[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.weights[range].iter().copied())
+    }
+
+    fn neighbours(&self, v: usize) -> impl Iterator<Item = usize> {
+        self.targets[self.offsets[v]..self.offsets[v + 1]]
+            .iter()
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bfs, dfs, scc, AdjacencyList};
+
+    const GRAPH: &str = r"1 2
+2 3
+2 4
+3 1
+3 5
+4 6
+5 4
+6 5";
+
+    #[test]
+    fn new_from_edge_list_test() {
+        let graph = CsrGraph::new_from_edge_list(GRAPH).unwrap();
+
+        assert_eq!(graph.offsets, vec![0, 1, 3, 5, 6, 7, 8]);
+        assert_eq!(graph.targets, vec![1, 2, 3, 0, 4, 5, 3, 4]);
+        assert_eq!(graph.neighbours(1).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(graph.edge_count(), 8);
+
+        assert_eq!(
+            CsrGraph::new_from_edge_list("1 2\n2").unwrap_err(),
+            ParseError::new(2, "a 1-indexed vertex", None)
+        );
+        assert_eq!(
+            CsrGraph::new_from_edge_list("1 2\n2 3 4").unwrap_err(),
+            ParseError::new(2, "the end of the line", Some("4"))
+        );
+    }
+
+    #[test]
+    fn transposed_test() {
+        let graph = CsrGraph::new_from_edges(3, [(0, 1, 5), (0, 2, 3), (2, 1, 1)]);
+        let transposed = graph.transposed();
+
+        assert_eq!(
+            transposed.weighted_neighbours(1).collect::<Vec<_>>(),
+            vec![(0, 5), (2, 1)]
+        );
+        assert_eq!(
+            transposed.weighted_neighbours(2).collect::<Vec<_>>(),
+            vec![(0, 3)]
+        );
+        assert_eq!(transposed.transposed(), graph);
+    }
+
+    #[test]
+    fn algorithms_test() {
+        let csr = CsrGraph::new_from_edge_list(GRAPH).unwrap();
+        let adj = AdjacencyList::new_from_edges(
+            csr.vertex_count(),
+            csr.edges().map(|(u, v, _)| (u, v, 1)),
+        );
+
+        assert_eq!(bfs::shortest_paths(&csr, 0), bfs::shortest_paths(&adj, 0));
+        assert_eq!(dfs::topological_sort(&csr), dfs::topological_sort(&adj));
+        assert!(dfs::topological_sort(&csr).is_err());
+        assert_eq!(scc::kosaraju(&csr), scc::kosaraju(&adj));
+    }
+}
@@ -0,0 +1,199 @@
+//! Paths on weighted DAGs, in a single pass over a topological order.
+
+use std::fmt;
+
+use crate::{
+    dfs::{topological_sort, Cycle},
+    Graph, Paths,
+};
+
+pub fn shortest_paths<G: Graph<Weight = usize>>(graph: &G, start: usize) -> Result<Paths, Cycle> {
+    relax(graph, start, |new, old| new < old)
+}
+
+pub fn longest_paths<G: Graph<Weight = usize>>(graph: &G, start: usize) -> Result<Paths, Cycle> {
+    relax(graph, start, |new, old| new > old)
+}
+
+/// Every vertex is final once the vertices before it in topological order
+/// have relaxed their edges.
+fn relax<G, F>(graph: &G, start: usize, better: F) -> Result<Paths, Cycle>
+where
+    G: Graph<Weight = usize>,
+    F: Fn(usize, usize) -> bool,
+{
+    let order = topological_sort(graph)?;
+
+    let mut paths = Paths::new(graph.vertex_count(), &[start]);
+
+    for u in order {
+        let Some(distance) = paths.distances[u] else {
+            continue;
+        };
+        for (v, c) in graph.weighted_neighbours(u) {
+            if paths.distances[v].is_none_or(|old| better(distance + c, old)) {
+                paths.distances[v] = Some(distance + c);
+                paths.parents[v] = Some(u);
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+/// The schedule of a project whose edges are tasks taking their weight in
+/// time, and whose vertices are the events of all tasks into them being
+/// done, after which the tasks out of them can start.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriticalPath {
+    pub length: usize,        // time the whole project takes
+    pub earliest: Vec<usize>, // earliest time every event can happen
+    pub latest: Vec<usize>,   // latest time it can happen without delaying the project
+    pub path: Vec<usize>,     // events along a longest path, none of them can slip
+}
+
+impl CriticalPath {
+    /// How long an event can slip without delaying the project.
+    pub fn slack(&self, v: usize) -> usize {
+        self.latest[v] - self.earliest[v]
+    }
+}
+
+impl fmt::Display for CriticalPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "vertex\tearliest\tlatest\tslack")?;
+        for v in 0..self.earliest.len() {
+            let critical = if self.slack(v) == 0 { "\t*" } else { "" };
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}{}",
+                v + 1,
+                self.earliest[v],
+                self.latest[v],
+                self.slack(v),
+                critical
+            )?;
+        }
+
+        let path = self
+            .path
+            .iter()
+            .map(|v| (v + 1).to_string())
+            .collect::<Vec<_>>();
+        write!(f, "critical path: {} ({})", path.join(" -> "), self.length)
+    }
+}
+
+pub fn critical_path<G: Graph<Weight = usize>>(graph: &G) -> Result<CriticalPath, Cycle> {
+    let order = topological_sort(graph)?;
+    let n = graph.vertex_count();
+
+    // forwards every event waits for the tasks into it
+    let mut earliest = vec![0; n];
+    let mut parents = vec![None; n];
+    for &u in order.iter() {
+        for (v, c) in graph.weighted_neighbours(u) {
+            if earliest[u] + c > earliest[v]
+                || parents[v].is_none() && earliest[u] + c == earliest[v]
+            {
+                earliest[v] = earliest[u] + c;
+                parents[v] = Some(u);
+            }
+        }
+    }
+    let length = earliest.iter().copied().max().unwrap_or(0);
+
+    // and backwards every event must leave its tasks enough time
+    let mut latest = vec![length; n];
+    for &u in order.iter().rev() {
+        for (v, c) in graph.weighted_neighbours(u) {
+            latest[u] = latest[u].min(latest[v] - c);
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut vertex = (0..n).find(|&v| earliest[v] == length);
+    while let Some(v) = vertex {
+        path.push(v);
+        vertex = parents[v];
+    }
+    path.reverse();
+
+    Ok(CriticalPath {
+        length,
+        earliest,
+        latest,
+        path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dijkstra, generate, AdjacencyList};
+
+    // 0 -> 1 -> 3 takes 3 + 4, 0 -> 2 -> 3 takes 2 + 1 and 2 -> 4 takes 6
+    const EDGES: [(usize, usize, usize); 5] =
+        [(0, 1, 3), (0, 2, 2), (1, 3, 4), (2, 3, 1), (2, 4, 6)];
+
+    #[test]
+    fn paths_test() {
+        let graph = AdjacencyList::new_from_edges(6, EDGES);
+
+        let shortest = shortest_paths(&graph, 0).unwrap();
+        assert_eq!(
+            shortest.distances,
+            vec![Some(0), Some(3), Some(2), Some(3), Some(8), None]
+        );
+        assert_eq!(shortest.path_to(3), Some(vec![0, 2, 3]));
+
+        let longest = longest_paths(&graph, 0).unwrap();
+        assert_eq!(longest.distances[3], Some(7));
+        assert_eq!(longest.path_to(3), Some(vec![0, 1, 3]));
+        assert_eq!(longest.path_to(5), None);
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        let (graph, order) = generate::dag(60, 0.1, 8);
+        let graph = generate::with_random_weights(&graph, 20, 8);
+
+        assert_eq!(
+            shortest_paths(&graph, order[0]).unwrap().distances,
+            dijkstra::dijkstras_algorithm(&graph, order[0]).distances
+        );
+    }
+
+    #[test]
+    fn critical_path_test() {
+        let graph = AdjacencyList::new_from_edges(5, EDGES);
+
+        let schedule = critical_path(&graph).unwrap();
+        assert_eq!(schedule.length, 8);
+        assert_eq!(schedule.path, vec![0, 2, 4]);
+        assert_eq!(schedule.earliest, vec![0, 3, 2, 7, 8]);
+        assert_eq!(schedule.latest, vec![0, 4, 2, 8, 8]);
+        assert_eq!(schedule.slack(1), 1);
+        assert_eq!(
+            schedule.to_string(),
+            "vertex\tearliest\tlatest\tslack
+1\t0\t0\t0\t*
+2\t3\t4\t1
+3\t2\t2\t0\t*
+4\t7\t8\t1
+5\t8\t8\t0\t*
+critical path: 1 -> 3 -> 5 (8)"
+        );
+    }
+
+    #[test]
+    fn cycle_test() {
+        let graph = AdjacencyList::new_from_edges(3, [(0, 1, 1), (1, 2, 1), (2, 1, 1)]);
+
+        assert_eq!(shortest_paths(&graph, 0), Err(Cycle(vec![1, 2])));
+        assert_eq!(
+            critical_path(&graph).unwrap_err().to_string(),
+            "the graph has a cycle: 2 -> 3 -> 2"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use std::{cmp::Reverse, collections::BinaryHeap, error::Error, fmt};
+
+use crate::Graph;
+
+/// Callbacks of a depth first search, each defaults to doing nothing.
+///
+/// Edges are classified for directed graphs, an undirected graph reports
+/// every edge from both ends, the second time as a back edge.
+pub trait Visitor {
+    /// A new tree starts at `root`, right before it is discovered.
+    fn start(&mut self, _root: usize) {}
+
+    fn discover(&mut self, _vertex: usize, _time: usize) {}
+
+    /// Every vertex reachable from `vertex` has been discovered.
+    fn finish(&mut self, _vertex: usize, _time: usize) {}
+
+    /// `head` is discovered through this edge.
+    fn tree_edge(&mut self, _tail: usize, _head: usize) {}
+
+    /// `head` is an ancestor of `tail` that hasn't finished, the edge closes a cycle.
+    fn back_edge(&mut self, _tail: usize, _head: usize) {}
+
+    /// `head` is a descendant of `tail` that has finished.
+    fn forward_edge(&mut self, _tail: usize, _head: usize) {}
+
+    /// `head` finished in another subtree or an earlier tree.
+    fn cross_edge(&mut self, _tail: usize, _head: usize) {}
+}
+
+/// When every vertex was discovered and finished, on a single clock that
+/// ticks at every discovery and finish. `None` for vertices never reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timestamps {
+    pub discovered: Vec<Option<usize>>,
+    pub finished: Vec<Option<usize>>,
+}
+
+/// Searches from each of `roots` that an earlier tree hasn't reached yet,
+/// making the same calls in the same order as the recursive search would,
+/// but with an explicit stack so deep graphs don't overflow the call stack.
+pub fn depth_first<G, V>(
+    graph: &G,
+    roots: impl IntoIterator<Item = usize>,
+    visitor: &mut V,
+) -> Timestamps
+where
+    G: Graph,
+    V: Visitor,
+{
+    let n = graph.vertex_count();
+    let mut times = Timestamps {
+        discovered: vec![None; n],
+        finished: vec![None; n],
+    };
+    let mut clock = 0;
+    // every vertex on the path to the current one, with the rest of its neighbours
+    let mut stack = Vec::new();
+
+    for root in roots {
+        if times.discovered[root].is_some() {
+            continue;
+        }
+
+        visitor.start(root);
+        times.discovered[root] = Some(clock);
+        visitor.discover(root, clock);
+        clock += 1;
+        stack.push((root, graph.neighbours(root)));
+
+        while let Some((vertex, neighbours)) = stack.last_mut() {
+            let vertex = *vertex;
+            let mut tree_edge = None;
+
+            for neighbour in neighbours.by_ref() {
+                match (times.discovered[neighbour], times.finished[neighbour]) {
+                    (None, _) => {
+                        tree_edge = Some(neighbour);
+                        break;
+                    }
+                    (Some(_), None) => visitor.back_edge(vertex, neighbour),
+                    (Some(t), Some(_)) if t > times.discovered[vertex].unwrap() => {
+                        visitor.forward_edge(vertex, neighbour)
+                    }
+                    (Some(_), Some(_)) => visitor.cross_edge(vertex, neighbour),
+                }
+            }
+
+            match tree_edge {
+                Some(neighbour) => {
+                    visitor.tree_edge(vertex, neighbour);
+                    times.discovered[neighbour] = Some(clock);
+                    visitor.discover(neighbour, clock);
+                    clock += 1;
+                    stack.push((neighbour, graph.neighbours(neighbour)));
+                }
+                None => {
+                    stack.pop();
+                    times.finished[vertex] = Some(clock);
+                    visitor.finish(vertex, clock);
+                    clock += 1;
+                }
+            }
+        }
+    }
+
+    times
+}
+
+/// A cycle of a directed graph, each vertex has an edge to the next and the
+/// last to the first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle(pub Vec<usize>);
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let vertices = self
+            .0
+            .iter()
+            .chain(self.0.first())
+            .map(|v| (v + 1).to_string())
+            .collect::<Vec<_>>();
+
+        write!(f, "the graph has a cycle: {}", vertices.join(" -> "))
+    }
+}
+
+impl Error for Cycle {}
+
+/// Collects vertices as they finish, and the first cycle a back edge closes.
+struct ReversePostorder {
+    finished: Vec<usize>,
+    parents: Vec<Option<usize>>,
+    cycle: Option<Cycle>,
+}
+
+impl Visitor for ReversePostorder {
+    fn finish(&mut self, vertex: usize, _time: usize) {
+        self.finished.push(vertex);
+    }
+
+    fn tree_edge(&mut self, tail: usize, head: usize) {
+        self.parents[head] = Some(tail);
+    }
+
+    fn back_edge(&mut self, tail: usize, head: usize) {
+        if self.cycle.is_some() {
+            return;
+        }
+
+        // head is an ancestor of tail, the tree path between them closes the cycle
+        let mut cycle = vec![tail];
+        let mut vertex = tail;
+        while vertex != head {
+            vertex = self.parents[vertex].unwrap();
+            cycle.push(vertex);
+        }
+        cycle.reverse();
+
+        self.cycle = Some(Cycle(cycle));
+    }
+}
+
+/// Orders the vertices so every edge goes from an earlier to a later one,
+/// or returns a cycle when there is no such order.
+pub fn topological_sort<G: Graph>(graph: &G) -> Result<Vec<usize>, Cycle> {
+    let mut visitor = ReversePostorder {
+        finished: Vec::with_capacity(graph.vertex_count()),
+        parents: vec![None; graph.vertex_count()],
+        cycle: None,
+    };
+    depth_first(graph, graph.vertices(), &mut visitor);
+
+    match visitor.cycle {
+        Some(cycle) => Err(cycle),
+        None => {
+            visitor.finished.reverse();
+            Ok(visitor.finished)
+        }
+    }
+}
+
+/// Kahn's algorithm, always taking the smallest vertex no remaining edge
+/// points to, which gives the lexicographically smallest topological order.
+pub fn kahn<G: Graph>(graph: &G) -> Result<Vec<usize>, Cycle> {
+    let mut in_degrees = vec![0; graph.vertex_count()];
+    for (_, v, _) in graph.edges() {
+        in_degrees[v] += 1;
+    }
+
+    let mut ready = graph
+        .vertices()
+        .filter(|&v| in_degrees[v] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<_>>();
+    let mut order = Vec::with_capacity(graph.vertex_count());
+
+    while let Some(Reverse(u)) = ready.pop() {
+        order.push(u);
+        for v in graph.neighbours(u) {
+            in_degrees[v] -= 1;
+            if in_degrees[v] == 0 {
+                ready.push(Reverse(v));
+            }
+        }
+    }
+
+    if order.len() < graph.vertex_count() {
+        // the vertices left over all lie on or after a cycle, let the search find one
+        return Err(topological_sort(graph).unwrap_err());
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, AdjacencyList};
+
+    /// Writes down every call as a string.
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl Visitor for Recorder {
+        fn start(&mut self, root: usize) {
+            self.0.push(format!("start {}", root));
+        }
+
+        fn discover(&mut self, vertex: usize, time: usize) {
+            self.0.push(format!("discover {} at {}", vertex, time));
+        }
+
+        fn finish(&mut self, vertex: usize, time: usize) {
+            self.0.push(format!("finish {} at {}", vertex, time));
+        }
+
+        fn tree_edge(&mut self, tail: usize, head: usize) {
+            self.0.push(format!("tree {} {}", tail, head));
+        }
+
+        fn back_edge(&mut self, tail: usize, head: usize) {
+            self.0.push(format!("back {} {}", tail, head));
+        }
+
+        fn forward_edge(&mut self, tail: usize, head: usize) {
+            self.0.push(format!("forward {} {}", tail, head));
+        }
+
+        fn cross_edge(&mut self, tail: usize, head: usize) {
+            self.0.push(format!("cross {} {}", tail, head));
+        }
+    }
+
+    #[test]
+    fn depth_first_test() {
+        let graph = AdjacencyList::new_from_edges(
+            5,
+            [(0, 1, 1), (0, 2, 1), (1, 2, 1), (2, 0, 1), (3, 0, 1)],
+        );
+
+        let mut recorder = Recorder::default();
+        let times = depth_first(&graph, [0, 3, 4], &mut recorder);
+
+        assert_eq!(
+            recorder.0,
+            vec![
+                "start 0",
+                "discover 0 at 0",
+                "tree 0 1",
+                "discover 1 at 1",
+                "tree 1 2",
+                "discover 2 at 2",
+                "back 2 0",
+                "finish 2 at 3",
+                "finish 1 at 4",
+                "forward 0 2",
+                "finish 0 at 5",
+                "start 3",
+                "discover 3 at 6",
+                "cross 3 0",
+                "finish 3 at 7",
+                "start 4",
+                "discover 4 at 8",
+                "finish 4 at 9",
+            ]
+        );
+        assert_eq!(times.discovered[2], Some(2));
+        assert_eq!(times.finished[0], Some(5));
+    }
+
+    /// Whether every edge of `graph` goes forward in `order`.
+    fn is_topological<G: Graph>(graph: &G, order: &[usize]) -> bool {
+        let mut position = vec![0; order.len()];
+        for (i, &v) in order.iter().enumerate() {
+            position[v] = i;
+        }
+
+        graph.edges().all(|(u, v, _)| position[u] < position[v])
+    }
+
+    #[test]
+    fn deep_test() {
+        // a path far longer than the call stack could recurse along
+        let n = 1_000_000;
+        let graph = AdjacencyList::new_from_edges(n, (1..n).map(|v| (v - 1, v, 1)));
+
+        assert_eq!(topological_sort(&graph), Ok((0..n).collect()));
+    }
+
+    #[test]
+    fn topological_sort_test() {
+        let graph = AdjacencyList::new_from_edges(4, [(0, 1, 1), (0, 2, 1), (1, 3, 1), (2, 3, 1)]);
+
+        let order = topological_sort(&graph).unwrap();
+        assert_eq!(order[0], 0);
+        assert_eq!(order[3], 3);
+
+        let (graph, _) = generate::dag(50, 0.1, 3);
+        assert!(is_topological(&graph, &topological_sort(&graph).unwrap()));
+    }
+
+    #[test]
+    fn cycle_test() {
+        // 0 -> 1 -> 2 -> 3 -> 1 and 3 -> 4
+        let graph = AdjacencyList::new_from_edges(
+            5,
+            [(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 1, 1), (3, 4, 1)],
+        );
+
+        let cycle = topological_sort(&graph).unwrap_err();
+        assert_eq!(cycle, Cycle(vec![1, 2, 3]));
+        assert_eq!(cycle.to_string(), "the graph has a cycle: 2 -> 3 -> 4 -> 2");
+        assert_eq!(kahn(&graph), Err(cycle));
+
+        let graph = AdjacencyList::new_from_edges(2, [(0, 1, 1), (1, 1, 1)]);
+        assert_eq!(topological_sort(&graph), Err(Cycle(vec![1])));
+    }
+
+    #[test]
+    fn kahn_test() {
+        // both 3 and 0 are free to go first, and then 1 or 2
+        let graph = AdjacencyList::new_from_edges(5, [(3, 1, 1), (3, 2, 1), (0, 4, 1), (2, 4, 1)]);
+        assert_eq!(kahn(&graph), Ok(vec![0, 3, 1, 2, 4]));
+
+        let (graph, _) = generate::dag(50, 0.1, 4);
+        assert!(is_topological(&graph, &kahn(&graph).unwrap()));
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    priority_queue::{IndexedPriorityQueue, PriorityQueue},
+    Graph, Paths, Weight,
+};
+
+/// Returns the distance to and parent of every vertex on its shortest path
+/// from `start`. The weights must not be negative.
+pub fn dijkstras_algorithm<G>(graph: &G, start: usize) -> Paths<G::Weight>
+where
+    G: Graph,
+    G::Weight: Weight,
+{
+    search::<IndexedPriorityQueue<G::Weight>, G>(graph, start, None)
+}
+
+/// Same as `dijkstras_algorithm` but on the priority queue `Q`.
+pub fn dijkstras_algorithm_with<Q, G>(graph: &G, start: usize) -> Paths<G::Weight>
+where
+    Q: PriorityQueue<G::Weight>,
+    G: Graph,
+    G::Weight: Weight,
+{
+    search::<Q, G>(graph, start, None)
+}
+
+/// Same as `dijkstras_algorithm` but stops as soon as `target` is settled,
+/// so only the vertices settled by then are sure to have their final
+/// distance.
+pub fn shortest_path<G>(graph: &G, start: usize, target: usize) -> Paths<G::Weight>
+where
+    G: Graph,
+    G::Weight: Weight,
+{
+    search::<IndexedPriorityQueue<G::Weight>, G>(graph, start, Some(target))
+}
+
+fn search<Q, G>(graph: &G, start: usize, target: Option<usize>) -> Paths<G::Weight>
+where
+    Q: PriorityQueue<G::Weight>,
+    G: Graph,
+    G::Weight: Weight,
+{
+    let mut paths = Paths::new(graph.vertex_count(), &[start]);
+
+    let mut queue = Q::new(graph.vertex_count());
+    queue.push(start, G::Weight::ZERO);
+
+    while let Some((current_vertex, current_distance)) = queue.pop_min() {
+        if target == Some(current_vertex) {
+            break;
+        }
+
+        for (neighbour_vertex, neighbour_distance) in graph.weighted_neighbours(current_vertex) {
+            let distance = current_distance.saturating_add(neighbour_distance);
+            if paths.distances[neighbour_vertex].is_some_and(|old| old <= distance) {
+                continue;
+            }
+
+            paths.distances[neighbour_vertex] = Some(distance);
+            paths.parents[neighbour_vertex] = Some(current_vertex);
+            queue.push_or_decrease(neighbour_vertex, distance);
+        }
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        generate,
+        priority_queue::{FibonacciHeap, PairingHeap},
+        weight::OrderedFloat,
+        AdjacencyList,
+    };
+    use std::time::Instant;
+
+    #[test]
+    fn small_test() {
+        let graph = AdjacencyList::new_from_edges(
+            4,
+            [(0, 1, 1), (0, 2, 4), (1, 2, 2), (1, 3, 6), (2, 3, 3)],
+        );
+
+        let paths = dijkstras_algorithm(&graph, 0);
+        assert_eq!(paths.distances, vec![Some(0), Some(1), Some(3), Some(6)]);
+        assert_eq!(paths.path_to(3), Some(vec![0, 1, 2, 3]));
+
+        let paths = dijkstras_algorithm(&graph, 3);
+        assert_eq!(paths.distances, vec![None, None, None, Some(0)]);
+        assert_eq!(paths.path_to(0), None);
+    }
+
+    #[test]
+    fn float_test() {
+        // latencies in milliseconds
+        let graph = AdjacencyList::new_from_edges(
+            4,
+            [
+                (0, 1, 0.5),
+                (0, 2, 2.25),
+                (1, 2, 1.0),
+                (2, 3, 0.125),
+                (1, 3, 4.0),
+            ]
+            .map(|(u, v, c)| (u, v, OrderedFloat(c))),
+        );
+
+        let paths = dijkstras_algorithm(&graph, 0);
+        assert_eq!(paths.distance(3), Some(OrderedFloat(1.625)));
+        assert_eq!(paths.path_to(3), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn shortest_path_test() {
+        // 0 -> 1 is settled long before anything around 2 and 3
+        let graph = AdjacencyList::new_from_edges(4, [(0, 1, 1), (0, 2, 10), (2, 3, 1), (1, 0, 1)]);
+
+        let paths = shortest_path(&graph, 0, 1);
+        assert_eq!(paths.path_to(1), Some(vec![0, 1]));
+        assert_eq!(paths.distance(3), None);
+
+        let (road, _) = generate::road(12, 12, 6);
+        let full = dijkstras_algorithm(&road, 0);
+        for target in [1, 50, 143] {
+            let paths = shortest_path(&road, 0, target);
+            assert_eq!(paths.distance(target), full.distance(target));
+
+            let path = paths.path_to(target).unwrap();
+            let length = path
+                .windows(2)
+                .map(|w| road.adj[w[0]].iter().find(|&&(v, _)| v == w[1]).unwrap().1)
+                .sum::<usize>();
+            assert_eq!(Some(length), full.distance(target));
+        }
+    }
+
+    fn queue_graphs() -> [(&'static str, AdjacencyList); 2] {
+        [
+            (
+                "sparse",
+                generate::with_random_weights(&generate::gnp(2000, 0.003, true, 7), 100, 7),
+            ),
+            (
+                "dense",
+                generate::with_random_weights(&generate::gnp(400, 0.5, true, 8), 100, 8),
+            ),
+        ]
+    }
+
+    #[test]
+    fn queues_test() {
+        for (_, graph) in queue_graphs() {
+            let expected = dijkstras_algorithm(&graph, 0);
+            let pairing = dijkstras_algorithm_with::<PairingHeap<usize>, _>(&graph, 0);
+            let fibonacci = dijkstras_algorithm_with::<FibonacciHeap<usize>, _>(&graph, 0);
+
+            assert_eq!(pairing.distances, expected.distances);
+            assert_eq!(fibonacci.distances, expected.distances);
+        }
+    }
+
+    /// Times the three queues against each other, which only means anything
+    /// in an optimised build:
+    /// `cargo test --release -- --ignored queues_benchmark --nocapture`
+    #[test]
+    #[ignore]
+    fn queues_benchmark() {
+        for (name, graph) in queue_graphs() {
+            let time = |run: fn(&AdjacencyList, usize) -> Paths| {
+                let now = Instant::now();
+                run(&graph, 0);
+                now.elapsed()
+            };
+
+            let d_ary = time(dijkstras_algorithm);
+            let pairing = time(dijkstras_algorithm_with::<PairingHeap<usize>, _>);
+            let fibonacci = time(dijkstras_algorithm_with::<FibonacciHeap<usize>, _>);
+
+            println!(
+                "{} ({} vertices, {} edges): d-ary {:?}, pairing {:?}, fibonacci {:?}",
+                name,
+                graph.vertex_count(),
+                graph.edge_count(),
+                d_ary,
+                pairing,
+                fibonacci
+            );
+        }
+    }
+}
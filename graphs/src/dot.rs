@@ -0,0 +1,314 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Write},
+};
+
+use crate::{Graph, Paths, SymbolTable};
+
+const PALETTE: [&str; 12] = [
+    "lightblue",
+    "lightpink",
+    "palegreen",
+    "khaki",
+    "plum",
+    "lightsalmon",
+    "paleturquoise",
+    "wheat",
+    "thistle",
+    "lightcoral",
+    "lightcyan",
+    "lightgoldenrod",
+];
+
+/// Algorithm output to draw on top of a graph, `W` being the weight of its
+/// edges. Vertices filled by more than one overlay are split into wedges of
+/// every colour they were given.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overlay<'a, W = usize> {
+    /// Fills every vertex with the colour of its component, given the leader
+    /// of the component of every vertex like `scc::kosaraju` returns.
+    Components(&'a [usize]),
+    /// Draws the given (tail, head) edges in bold, like the edges of
+    /// `mst::prims_tree` or `mst::kruskals_tree`.
+    Edges(&'a [(usize, usize)]),
+    /// Fills the vertices on either side of a cut differently and dashes the
+    /// edges crossing it, `true` and `false` being the two sides.
+    Cut(&'a [bool]),
+    /// Labels every vertex with its distance and draws the edge from its
+    /// parent in bold, given the `Paths` of any shortest path search.
+    ShortestPaths(&'a Paths<W>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions<'a, W = usize> {
+    pub directed: bool,                  // undirected graphs get every edge drawn once
+    pub weights: bool,                   // label edges with their weights
+    pub labels: Option<&'a SymbolTable>, // vertex names, otherwise the 1-indexed vertex
+    pub overlays: Vec<Overlay<'a, W>>,
+}
+
+/// Renders `graph` in the Graphviz DOT language, e.g. for `dot -Tsvg`.
+pub fn to_dot<G>(graph: &G, options: &DotOptions<G::Weight>) -> String
+where
+    G: Graph,
+    G::Weight: fmt::Debug,
+{
+    let mut out = String::new();
+    write_dot(&mut out, graph, options).unwrap();
+
+    out
+}
+
+pub fn write_dot<G, O>(out: &mut O, graph: &G, options: &DotOptions<G::Weight>) -> fmt::Result
+where
+    G: Graph,
+    G::Weight: fmt::Debug,
+    O: Write,
+{
+    let (keyword, arrow) = if options.directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+    let key = |u: usize, v: usize| {
+        if options.directed {
+            (u, v)
+        } else {
+            (usize::min(u, v), usize::max(u, v))
+        }
+    };
+
+    // every overlay adds attributes to vertices and edges
+    let mut fills: Vec<Vec<&str>> = vec![Vec::new(); graph.vertex_count()];
+    let mut extra_label: Vec<Option<String>> = vec![None; graph.vertex_count()];
+    let mut bold: HashSet<(usize, usize)> = HashSet::new();
+    let mut cut: Option<&[bool]> = None;
+
+    for overlay in options.overlays.iter() {
+        match *overlay {
+            Overlay::Components(leaders) => {
+                let mut colours = HashMap::new();
+                for (v, leader) in leaders.iter().enumerate() {
+                    let next = colours.len();
+                    let colour = *colours
+                        .entry(leader)
+                        .or_insert(PALETTE[next % PALETTE.len()]);
+                    fills[v].push(colour);
+                }
+            }
+            Overlay::Edges(edges) => {
+                bold.extend(edges.iter().map(|&(u, v)| key(u, v)));
+            }
+            Overlay::Cut(side) => {
+                for (v, &s) in side.iter().enumerate() {
+                    let colour = if s { PALETTE[0] } else { PALETTE[1] };
+                    fills[v].push(colour);
+                }
+                cut = Some(side);
+            }
+            Overlay::ShortestPaths(paths) => {
+                for (v, d) in paths.distances.iter().enumerate() {
+                    extra_label[v] = Some(match d {
+                        Some(d) => format!("d={:?}", d),
+                        None => "d=inf".to_string(),
+                    });
+                }
+                bold.extend(
+                    paths
+                        .parents
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(v, p)| p.map(|u| key(u, v))),
+                );
+            }
+        }
+    }
+
+    writeln!(out, "{} {{", keyword)?;
+
+    for v in graph.vertices() {
+        let name = match options.labels {
+            Some(symbols) => symbols.label(v).to_string(),
+            None => (v + 1).to_string(),
+        };
+        let label = match &extra_label[v] {
+            Some(extra) => format!("{}\\n{}", escape(&name), extra),
+            None => escape(&name),
+        };
+
+        let mut attrs = vec![format!("label=\"{}\"", label)];
+        match fills[v].as_slice() {
+            [] => {}
+            [colour] => attrs.push(format!("style=filled, fillcolor={}", colour)),
+            colours => attrs.push(format!("style=wedged, fillcolor=\"{}\"", colours.join(":"))),
+        }
+        writeln!(out, "    n{} [{}];", v, attrs.join(", "))?;
+    }
+
+    for (u, v, c) in graph.edges() {
+        if !options.directed && u > v {
+            continue;
+        }
+
+        let mut attrs = Vec::new();
+        if options.weights {
+            attrs.push(format!("label=\"{:?}\"", c));
+        }
+        if bold.contains(&key(u, v)) {
+            attrs.push("penwidth=3".to_string());
+        }
+        if cut.is_some_and(|side| side[u] != side[v]) {
+            attrs.push("style=dashed, color=red".to_string());
+        }
+
+        if attrs.is_empty() {
+            writeln!(out, "    n{} {} n{};", u, arrow, v)?;
+        } else {
+            writeln!(out, "    n{} {} n{} [{}];", u, arrow, v, attrs.join(", "))?;
+        }
+    }
+
+    writeln!(out, "}}")
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dijkstra, mst, scc, weight::OrderedFloat, AdjacencyList};
+
+    fn undirected(edges: &[(usize, usize, usize)]) -> AdjacencyList {
+        let n = edges
+            .iter()
+            .map(|&(u, v, _)| usize::max(u, v) + 1)
+            .max()
+            .unwrap();
+        AdjacencyList::new_from_edges(
+            n,
+            edges.iter().flat_map(|&(u, v, c)| [(u, v, c), (v, u, c)]),
+        )
+    }
+
+    #[test]
+    fn plain_test() {
+        let graph = AdjacencyList::new_from_edges(2, [(0, 1, 7)]);
+        let mut symbols = SymbolTable::new();
+        symbols.intern("a \"quoted\" host");
+        symbols.intern("b");
+
+        let dot = to_dot(
+            &graph,
+            &DotOptions {
+                directed: true,
+                weights: true,
+                labels: Some(&symbols),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            dot,
+            "digraph {\n    n0 [label=\"a \\\"quoted\\\" host\"];\n    n1 [label=\"b\"];\n    n0 -> n1 [label=\"7\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn components_test() {
+        let graph = AdjacencyList::new_from_edges(3, [(0, 1, 1), (1, 0, 1), (1, 2, 1)]);
+        let leaders = scc::kosaraju(&graph);
+
+        let dot = to_dot(
+            &graph,
+            &DotOptions {
+                directed: true,
+                overlays: vec![Overlay::Components(&leaders)],
+                ..Default::default()
+            },
+        );
+
+        assert!(dot.contains("n0 [label=\"1\", style=filled, fillcolor=lightblue];"));
+        assert!(dot.contains("n1 [label=\"2\", style=filled, fillcolor=lightblue];"));
+        assert!(dot.contains("n2 [label=\"3\", style=filled, fillcolor=lightpink];"));
+
+        // with a cut on top every vertex gets both colours
+        let side = [true, false, false];
+        let dot = to_dot(
+            &graph,
+            &DotOptions {
+                directed: true,
+                overlays: vec![Overlay::Components(&leaders), Overlay::Cut(&side)],
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(dot.matches("style=wedged").count(), 3);
+        assert!(!dot.contains("style=filled"));
+        assert!(dot.contains("n0 [label=\"1\", style=wedged, fillcolor=\"lightblue:lightblue\"];"));
+        assert!(dot.contains("n2 [label=\"3\", style=wedged, fillcolor=\"lightpink:lightpink\"];"));
+    }
+
+    #[test]
+    fn tree_and_cut_test() {
+        let graph = undirected(&[(0, 1, 1), (1, 2, 5), (0, 2, 2), (2, 3, 1)]);
+        let tree = mst::kruskals_tree(&graph)
+            .iter()
+            .map(|&(u, v, _)| (u, v))
+            .collect::<Vec<_>>();
+        let side = [true, true, false, false];
+
+        let dot = to_dot(
+            &graph,
+            &DotOptions {
+                overlays: vec![Overlay::Edges(&tree), Overlay::Cut(&side)],
+                ..Default::default()
+            },
+        );
+
+        // every undirected edge is drawn once
+        assert_eq!(dot.matches(" -- ").count(), 4);
+        assert!(dot.contains("n0 -- n1 [penwidth=3];"));
+        assert!(dot.contains("n1 -- n2 [style=dashed, color=red];"));
+        assert!(dot.contains("n0 -- n2 [penwidth=3, style=dashed, color=red];"));
+        assert!(dot.contains("n2 -- n3 [penwidth=3];"));
+    }
+
+    #[test]
+    fn shortest_paths_test() {
+        let graph = AdjacencyList::new_from_edges(3, [(0, 1, 1), (0, 2, 4), (1, 2, 2)]);
+        let paths = dijkstra::dijkstras_algorithm(&graph, 0);
+
+        let dot = to_dot(
+            &graph,
+            &DotOptions {
+                directed: true,
+                overlays: vec![Overlay::ShortestPaths(&paths)],
+                ..Default::default()
+            },
+        );
+
+        assert!(dot.contains("n2 [label=\"3\\nd=3\"];"));
+        assert!(dot.contains("n0 -> n2;"));
+        assert!(dot.contains("n1 -> n2 [penwidth=3];"));
+
+        let graph = AdjacencyList::new_from_edges(
+            2,
+            [(0, 1, OrderedFloat(0.5)), (1, 0, OrderedFloat(0.25))],
+        );
+        let paths = dijkstra::dijkstras_algorithm(&graph, 1);
+
+        let dot = to_dot(
+            &graph,
+            &DotOptions {
+                directed: true,
+                overlays: vec![Overlay::ShortestPaths(&paths)],
+                ..Default::default()
+            },
+        );
+
+        assert!(dot.contains("n0 [label=\"1\\nd=OrderedFloat(0.25)\"];"));
+        assert!(dot.contains("n1 -> n0 [penwidth=3];"));
+    }
+}
@@ -0,0 +1,252 @@
+//! Walks that use every edge exactly once, by Hierholzer's algorithm.
+
+use std::{collections::HashMap, error::Error, fmt};
+
+use crate::Graph;
+
+/// Why a graph has no Eulerian path or circuit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotEulerian {
+    /// The vertices of a directed graph with more edges out than in or the
+    /// other way around, with their out-degree minus in-degree.
+    Unbalanced(Vec<(usize, isize)>),
+    /// The vertices of an undirected graph with an odd degree, with it.
+    OddDegree(Vec<(usize, usize)>),
+    /// The degrees are fine but this many edges can't be reached from the rest.
+    Disconnected(usize),
+}
+
+impl fmt::Display for NotEulerian {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NotEulerian::Unbalanced(vertices) => {
+                let vertices = vertices
+                    .iter()
+                    .map(|&(v, d)| format!("{} ({:+})", v + 1, d))
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "out-degree minus in-degree is off at vertices {}",
+                    vertices.join(", ")
+                )
+            }
+            NotEulerian::OddDegree(vertices) => {
+                let vertices = vertices
+                    .iter()
+                    .map(|&(v, d)| format!("{} ({})", v + 1, d))
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "too many vertices of odd degree: {}",
+                    vertices.join(", ")
+                )
+            }
+            NotEulerian::Disconnected(edges) => {
+                write!(f, "{} edges can't be reached from the rest", edges)
+            }
+        }
+    }
+}
+
+impl Error for NotEulerian {}
+
+/// The vertices of a walk along every edge once, the first and last being
+/// different only if they have to be. Undirected graphs hold both
+/// directions of every edge and each pair is walked once. Empty when there
+/// are no edges.
+pub fn eulerian_path<G: Graph>(graph: &G, directed: bool) -> Result<Vec<usize>, NotEulerian> {
+    hierholzer(graph, directed, false)
+}
+
+/// Same as `eulerian_path` but the walk has to end where it starts.
+pub fn eulerian_circuit<G: Graph>(graph: &G, directed: bool) -> Result<Vec<usize>, NotEulerian> {
+    hierholzer(graph, directed, true)
+}
+
+fn hierholzer<G: Graph>(
+    graph: &G,
+    directed: bool,
+    circuit: bool,
+) -> Result<Vec<usize>, NotEulerian> {
+    let n = graph.vertex_count();
+
+    // the edges leaving every vertex with an id, both directions of an
+    // undirected edge share theirs
+    let mut adj = vec![Vec::new(); n];
+    let mut unpaired: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    let mut m = 0;
+    for (u, v, _) in graph.edges() {
+        let paired = match directed {
+            true => None,
+            false => unpaired.get_mut(&(v, u)).and_then(|ids| ids.pop()),
+        };
+        let id = match paired {
+            Some(id) => id,
+            None => {
+                if !directed {
+                    unpaired.entry((u, v)).or_default().push(m);
+                }
+                m += 1;
+                m - 1
+            }
+        };
+        adj[u].push((v, id));
+    }
+
+    let start = if directed {
+        let mut balance = vec![0isize; n];
+        for (u, v, _) in graph.edges() {
+            balance[u] += 1;
+            balance[v] -= 1;
+        }
+        let unbalanced = (0..n)
+            .filter(|&v| balance[v] != 0)
+            .map(|v| (v, balance[v]))
+            .collect::<Vec<_>>();
+
+        let path_ends = unbalanced.len() == 2
+            && unbalanced.iter().any(|&(_, d)| d == 1)
+            && unbalanced.iter().any(|&(_, d)| d == -1);
+        if !unbalanced.is_empty() && (circuit || !path_ends) {
+            return Err(NotEulerian::Unbalanced(unbalanced));
+        }
+        unbalanced.iter().find(|&&(_, d)| d == 1).map(|&(v, _)| v)
+    } else {
+        let odd = (0..n)
+            .filter(|&v| adj[v].len() % 2 == 1)
+            .map(|v| (v, adj[v].len()))
+            .collect::<Vec<_>>();
+
+        if odd.len() > if circuit { 0 } else { 2 } {
+            return Err(NotEulerian::OddDegree(odd));
+        }
+        odd.first().map(|&(v, _)| v)
+    };
+
+    let Some(start) = start.or_else(|| (0..n).find(|&v| !adj[v].is_empty())) else {
+        return Ok(Vec::new());
+    };
+
+    // walk until stuck, which can only happen back at the start of the
+    // current detour, then back up and splice in detours from earlier vertices
+    let mut used = vec![false; m];
+    let mut next = vec![0; n];
+    let mut stack = vec![start];
+    let mut walk = Vec::with_capacity(m + 1);
+
+    while let Some(&u) = stack.last() {
+        while next[u] < adj[u].len() && used[adj[u][next[u]].1] {
+            next[u] += 1;
+        }
+
+        match adj[u].get(next[u]) {
+            Some(&(v, id)) => {
+                used[id] = true;
+                stack.push(v);
+            }
+            None => walk.push(stack.pop().unwrap()),
+        }
+    }
+    walk.reverse();
+
+    if walk.len() < m + 1 {
+        return Err(NotEulerian::Disconnected(m + 1 - walk.len()));
+    }
+
+    Ok(walk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AdjacencyList;
+
+    fn directed(n: usize, edges: &[(usize, usize)]) -> AdjacencyList {
+        AdjacencyList::new_from_edges(n, edges.iter().map(|&(u, v)| (u, v, 1)))
+    }
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> AdjacencyList {
+        AdjacencyList::new_from_edges(n, edges.iter().flat_map(|&(u, v)| [(u, v, 1), (v, u, 1)]))
+    }
+
+    /// Whether `walk` uses every edge of `edges` exactly once.
+    fn covers(walk: &[usize], edges: &[(usize, usize)], directed: bool) -> bool {
+        let mut walked = walk
+            .windows(2)
+            .map(|w| match directed {
+                true => (w[0], w[1]),
+                false => (w[0].min(w[1]), w[0].max(w[1])),
+            })
+            .collect::<Vec<_>>();
+        let mut edges = edges
+            .iter()
+            .map(|&(u, v)| match directed {
+                true => (u, v),
+                false => (u.min(v), u.max(v)),
+            })
+            .collect::<Vec<_>>();
+        walked.sort_unstable();
+        edges.sort_unstable();
+
+        walked == edges
+    }
+
+    #[test]
+    fn directed_test() {
+        let edges = [(0, 1), (1, 2), (2, 0), (0, 3), (3, 4), (4, 0)];
+        let graph = directed(5, &edges);
+
+        let circuit = eulerian_circuit(&graph, true).unwrap();
+        assert_eq!(circuit.first(), circuit.last());
+        assert!(covers(&circuit, &edges, true));
+
+        // dropping 4 -> 0 leaves a path from 0 to 4
+        let graph = directed(5, &edges[..5]);
+        let path = eulerian_path(&graph, true).unwrap();
+        assert_eq!((path[0], path[5]), (0, 4));
+        assert!(covers(&path, &edges[..5], true));
+
+        assert_eq!(
+            eulerian_circuit(&graph, true),
+            Err(NotEulerian::Unbalanced(vec![(0, 1), (4, -1)]))
+        );
+    }
+
+    #[test]
+    fn undirected_test() {
+        // a square with one diagonal and a doubled edge, only 1 and 3 are odd
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 0), (1, 3), (0, 2), (0, 2)];
+        let graph = undirected(4, &edges);
+
+        let path = eulerian_path(&graph, false).unwrap();
+        assert_eq!(path.len(), 8);
+        assert!([path[0], path[7]] == [1, 3] || [path[0], path[7]] == [3, 1]);
+        assert!(covers(&path, &edges, false));
+
+        let error = eulerian_circuit(&graph, false).unwrap_err();
+        assert_eq!(error, NotEulerian::OddDegree(vec![(1, 3), (3, 3)]));
+        assert_eq!(
+            error.to_string(),
+            "too many vertices of odd degree: 2 (3), 4 (3)"
+        );
+    }
+
+    #[test]
+    fn not_eulerian_test() {
+        // two separate triangles are balanced but can't be walked in one go
+        let graph = undirected(6, &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+        assert_eq!(
+            eulerian_circuit(&graph, false),
+            Err(NotEulerian::Disconnected(3))
+        );
+
+        // the center of a star has three more edges out than in
+        let graph = directed(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(
+            eulerian_path(&graph, true).unwrap_err().to_string(),
+            "out-degree minus in-degree is off at vertices 1 (+3), 2 (-1), 3 (-1), 4 (-1)"
+        );
+
+        assert_eq!(eulerian_path(&directed(3, &[]), true), Ok(Vec::new()));
+    }
+}
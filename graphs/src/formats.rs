@@ -0,0 +1,375 @@
+//! Readers and writers for the file formats public benchmark graphs come in.
+//!
+//! Every format numbers vertices from 1, the graphs read are 0-indexed.
+
+use std::{
+    fmt::{self, Display, Write},
+    str::FromStr,
+};
+
+use crate::{
+    parse::{Lines, ParseError, Tokens},
+    AdjacencyList, Graph,
+};
+
+/// Reads a DIMACS shortest path (`.gr`) file: `c` comment lines, one
+/// `p sp n m` problem line and then `m` arc lines `a u v w`.
+pub fn read_dimacs<W: FromStr + Copy>(input: &str) -> Result<AdjacencyList<W>, ParseError> {
+    let mut graph: Option<AdjacencyList<W>> = None;
+    let (mut m, mut problem_line, mut arcs) = (0, 0, 0);
+
+    for mut line in Lines::new(input) {
+        match line.next_str("a line starting with c, p or a")? {
+            "c" => continue,
+            "p" if graph.is_none() => {
+                let kind = line.next_str("sp")?;
+                if kind != "sp" {
+                    return Err(line.error("sp", Some(kind)));
+                }
+                let n = line.next("the number of vertices")?;
+                m = line.next("the number of arcs")?;
+                line.expect_end()?;
+                problem_line = line.line();
+                graph = Some(AdjacencyList::new(n));
+            }
+            "a" => {
+                let graph = graph
+                    .as_mut()
+                    .ok_or_else(|| line.error("the problem line", Some("a")))?;
+                let u = line.vertex_below(graph.vertex_count())?;
+                let v = line.vertex_below(graph.vertex_count())?;
+                let c = line.next("an arc length")?;
+                line.expect_end()?;
+
+                graph.adj[u].push((v, c));
+                arcs += 1;
+            }
+            token => return Err(line.error("a line starting with c, p or a", Some(token))),
+        }
+    }
+
+    let graph = graph.ok_or_else(|| ParseError::new(1, "the problem line", None))?;
+    if arcs != m {
+        return Err(ParseError::new(
+            problem_line,
+            &format!("{} arcs", m),
+            Some(&arcs.to_string()),
+        ));
+    }
+
+    Ok(graph)
+}
+
+/// Writes every edge of `graph` as an arc, so an undirected graph gets both
+/// directions of each of its edges.
+pub fn write_dimacs<G, O>(out: &mut O, graph: &G) -> fmt::Result
+where
+    G: Graph,
+    G::Weight: Display,
+    O: Write,
+{
+    writeln!(out, "p sp {} {}", graph.vertex_count(), graph.edge_count())?;
+    for (u, v, c) in graph.edges() {
+        writeln!(out, "a {} {} {}", u + 1, v + 1, c)?;
+    }
+
+    Ok(())
+}
+
+/// Reads a METIS graph file: a `n m [fmt]` header and then one line per
+/// vertex listing its neighbours, each followed by the weight of the edge
+/// when `fmt` is 1. METIS graphs are undirected and list every edge from
+/// both ends, unweighted edges get weight 1.
+///
+/// Unlike the other formats a blank line is a vertex without neighbours,
+/// and lines starting with `%` are comments.
+pub fn read_metis(input: &str) -> Result<AdjacencyList<usize>, ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.starts_with('%'))
+        .map(|(i, line)| Tokens::new(i + 1, line))
+        .skip_while(|line| line.is_empty());
+
+    let mut header = lines
+        .next()
+        .ok_or_else(|| ParseError::new(1, "the number of vertices and edges", None))?;
+    let n = header.next("the number of vertices")?;
+    let m: usize = header.next("the number of edges")?;
+    let weighted = if header.is_empty() {
+        false
+    } else {
+        // only the last digit, edge weights, is supported
+        match header.next_str("a format")? {
+            fmt if fmt.trim_start_matches('0').is_empty() => false,
+            fmt if fmt.trim_start_matches('0') == "1" => true,
+            fmt => return Err(header.error("a format of 0 or 1", Some(fmt))),
+        }
+    };
+    header.expect_end()?;
+
+    let mut graph = AdjacencyList::new(n);
+    let mut last = header.line();
+    for u in 0..n {
+        let mut line = lines.next().ok_or_else(|| {
+            ParseError::new(
+                last + 1,
+                &format!("the neighbours of vertex {}", u + 1),
+                None,
+            )
+        })?;
+        last = line.line();
+
+        while !line.is_empty() {
+            let v = line.vertex_below(n)?;
+            let c = if weighted {
+                line.next("an edge weight")?
+            } else {
+                1
+            };
+            graph.adj[u].push((v, c));
+        }
+    }
+
+    if let Some(mut line) = lines.find(|line| !line.is_empty()) {
+        let token = line.next_str("the end of the file")?;
+        return Err(line.error("the end of the file", Some(token)));
+    }
+    if graph.edge_count() != 2 * m {
+        return Err(ParseError::new(
+            header.line(),
+            &format!("{} edges listed from both ends", m),
+            Some(&graph.edge_count().to_string()),
+        ));
+    }
+
+    Ok(graph)
+}
+
+/// Writes an undirected `graph`, one that holds both directions of each of
+/// its edges, in the METIS format.
+pub fn write_metis<G, O>(out: &mut O, graph: &G, weights: bool) -> fmt::Result
+where
+    G: Graph,
+    G::Weight: Display,
+    O: Write,
+{
+    write!(out, "{} {}", graph.vertex_count(), graph.edge_count() / 2)?;
+    writeln!(out, "{}", if weights { " 1" } else { "" })?;
+
+    for u in graph.vertices() {
+        let line = graph
+            .weighted_neighbours(u)
+            .map(|(v, c)| {
+                if weights {
+                    format!("{} {}", v + 1, c)
+                } else {
+                    (v + 1).to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", line.join(" "))?;
+    }
+
+    Ok(())
+}
+
+/// Reads a directed weighted edge list: a `n m` header and then `m` lines
+/// `u v w`.
+pub fn read_edge_list<W: FromStr + Copy>(input: &str) -> Result<AdjacencyList<W>, ParseError> {
+    let mut lines = Lines::new(input);
+
+    let mut header = lines.expect_line("the number of vertices and edges")?;
+    let n = header.next("the number of vertices")?;
+    let m = header.next("the number of edges")?;
+    header.expect_end()?;
+
+    let mut graph = AdjacencyList::new(n);
+    for _ in 0..m {
+        let mut line = lines.expect_line("an edge")?;
+        let u = line.vertex_below(n)?;
+        let v = line.vertex_below(n)?;
+        let c = line.next("an edge weight")?;
+        line.expect_end()?;
+
+        graph.adj[u].push((v, c));
+    }
+
+    if let Some(mut line) = lines.next() {
+        let expected = format!("{} edges", m);
+        let token = line.next_str(&expected)?;
+        return Err(line.error(&expected, Some(token)));
+    }
+
+    Ok(graph)
+}
+
+pub fn write_edge_list<G, O>(out: &mut O, graph: &G) -> fmt::Result
+where
+    G: Graph,
+    G::Weight: Display,
+    O: Write,
+{
+    writeln!(out, "{} {}", graph.vertex_count(), graph.edge_count())?;
+    for (u, v, c) in graph.edges() {
+        writeln!(out, "{} {} {}", u + 1, v + 1, c)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::{bfs, dijkstra, mst, scc, CsrGraph};
+
+    #[test]
+    fn dimacs_test() {
+        let input = fs::read_to_string("./data/sample.gr").unwrap();
+        let graph = read_dimacs::<usize>(&input).unwrap();
+
+        assert_eq!(graph.edge_count(), 8);
+        assert_eq!(
+            dijkstra::dijkstras_algorithm(&graph, 0).distances,
+            vec![Some(0), Some(10), Some(10), Some(12), Some(10), Some(15)]
+        );
+
+        let mut out = String::new();
+        write_dimacs(&mut out, &graph).unwrap();
+        assert_eq!(read_dimacs::<usize>(&out).unwrap(), graph);
+    }
+
+    #[test]
+    fn metis_test() {
+        let input = fs::read_to_string("./data/sample.graph").unwrap();
+        let graph = read_metis(&input).unwrap();
+        assert_eq!(graph.edge_count(), 16);
+        assert_eq!(mst::kruskals_algorithm(&graph), 15);
+
+        let graph = read_metis("% comment\n3 2\n2\n1 3\n2\n\n").unwrap();
+        assert_eq!(
+            graph.adj,
+            vec![vec![(1, 1)], vec![(0, 1), (2, 1)], vec![(1, 1)]]
+        );
+
+        // the blank line is the isolated vertex 2
+        let graph = read_metis("3 1 001\n3 7\n\n1 7\n").unwrap();
+        assert_eq!(graph.adj, vec![vec![(2, 7)], vec![], vec![(0, 7)]]);
+
+        let mut out = String::new();
+        write_metis(&mut out, &graph, true).unwrap();
+        assert_eq!(out, "3 1 1\n3 7\n\n1 7\n");
+        assert_eq!(read_metis(&out).unwrap(), graph);
+    }
+
+    #[test]
+    fn edge_list_test() {
+        let input = fs::read_to_string("./data/sample.txt").unwrap();
+        let graph = read_edge_list::<isize>(&input).unwrap();
+
+        let mut out = String::new();
+        write_edge_list(&mut out, &graph).unwrap();
+        assert_eq!(out, input);
+    }
+
+    #[test]
+    fn malformed_test() {
+        let error =
+            |result: Result<AdjacencyList<usize>, ParseError>| result.unwrap_err().to_string();
+
+        assert_eq!(
+            error(read_dimacs("p sp 2 1\na 1 3 5\n")),
+            "2: expected a vertex between 1 and 2, found `3`"
+        );
+        assert_eq!(
+            error(read_dimacs("a 1 2 5\n")),
+            "1: expected the problem line, found `a`"
+        );
+        assert_eq!(
+            error(read_dimacs("c\np sp 2 2\na 1 2 5\n")),
+            "2: expected 2 arcs, found `1`"
+        );
+        assert_eq!(
+            error(read_metis("2 1 011\n2 1 1\n1 1 1\n")),
+            "1: expected a format of 0 or 1, found `011`"
+        );
+        assert_eq!(
+            error(read_metis("3 1\n2\n1\n")),
+            "4: expected the neighbours of vertex 3, found nothing"
+        );
+        assert_eq!(
+            error(read_metis("2 2\n2\n1\n")),
+            "1: expected 2 edges listed from both ends, found `2`"
+        );
+        assert_eq!(
+            error(read_dimacs("p sp 2 1\na 1 2 5 6\n")),
+            "2: expected the end of the line, found `6`"
+        );
+        assert_eq!(
+            error(read_edge_list("2 1\n1 2 3 4\n")),
+            "2: expected the end of the line, found `4`"
+        );
+        assert_eq!(
+            error(read_edge_list("2 2\n1 2 3\n")),
+            "3: expected an edge, found nothing"
+        );
+        assert_eq!(
+            error(read_edge_list("2 1\n1 2 3\n\n2 1 3\n")),
+            "4: expected 1 edges, found `2`"
+        );
+        assert_eq!(
+            error(read_metis("2 1\n2\n1\n\n1 2\n")),
+            "5: expected the end of the file, found `1`"
+        );
+    }
+
+    /// Runs the algorithms over every graph in `./data`, benchmark graphs
+    /// downloaded there get checked the same way.
+    #[test]
+    fn data_test() {
+        for entry in fs::read_dir("./data").unwrap() {
+            let path = entry.unwrap().path();
+            let input = fs::read_to_string(&path).unwrap();
+            let file = path.to_str().unwrap();
+
+            match path.extension().and_then(|e| e.to_str()) {
+                Some("gr") => {
+                    let graph = read_dimacs::<usize>(&input)
+                        .map_err(|e| e.in_file(file))
+                        .unwrap();
+                    let csr = CsrGraph::new_from_graph(&graph);
+
+                    assert_eq!(
+                        dijkstra::dijkstras_algorithm(&graph, 0),
+                        dijkstra::dijkstras_algorithm(&csr, 0),
+                    );
+                    assert_eq!(scc::kosaraju(&graph), scc::kosaraju(&csr));
+                }
+                Some("graph") => {
+                    let graph = read_metis(&input).map_err(|e| e.in_file(file)).unwrap();
+                    let signed = AdjacencyList::new_from_edges(
+                        graph.vertex_count(),
+                        graph.edges().map(|(u, v, c)| (u, v, c as isize)),
+                    );
+
+                    // the components of an undirected graph are strongly connected
+                    let mut leaders = scc::kosaraju(&graph);
+                    leaders.sort_unstable();
+                    leaders.dedup();
+                    let components = bfs::connected_components(&graph);
+                    assert_eq!(leaders.len(), components.len());
+
+                    if components.len() == 1 {
+                        assert_eq!(
+                            mst::prims_algorithm(&signed, 0),
+                            mst::kruskals_algorithm(&graph) as isize
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,367 @@
+//! Seeded random graphs for tests and benchmarks.
+//!
+//! The same seed always gives the same graph. Undirected graphs hold both
+//! directions of every edge like every other undirected graph in the crate,
+//! and unweighted graphs use weight 1.
+
+use std::{collections::HashMap, fmt::Write};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{union_find::UnionFind, AdjacencyList, Graph};
+
+/// Erdős–Rényi G(n, p): every edge is there with probability `p`.
+pub fn gnp(n: usize, p: f64, directed: bool, seed: u64) -> AdjacencyList {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut graph = AdjacencyList::new(n);
+
+    for u in 0..n {
+        let heads = if directed { 0..n } else { u + 1..n };
+        for v in heads {
+            if u != v && rng.gen_bool(p) {
+                graph.adj[u].push((v, 1));
+                if !directed {
+                    graph.adj[v].push((u, 1));
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+/// A DAG with every edge from an earlier to a later vertex of a random order
+/// there with probability `p`. Returns the graph and the order.
+pub fn dag(n: usize, p: f64, seed: u64) -> (AdjacencyList, Vec<usize>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut order = (0..n).collect::<Vec<_>>();
+    order.shuffle(&mut rng);
+
+    let mut graph = AdjacencyList::new(n);
+    for i in 0..n {
+        for j in i + 1..n {
+            if rng.gen_bool(p) {
+                graph.adj[order[i]].push((order[j], 1));
+            }
+        }
+    }
+
+    (graph, order)
+}
+
+/// The undirected `rows` by `cols` grid, vertex `r * cols + c` sits at
+/// (c, r).
+pub fn grid(rows: usize, cols: usize) -> (AdjacencyList, Vec<(f64, f64)>) {
+    let mut graph = AdjacencyList::new(rows * cols);
+    for (u, v) in grid_edges(rows, cols) {
+        graph.adj[u].push((v, 1));
+        graph.adj[v].push((u, 1));
+    }
+
+    (graph, grid_coordinates(rows, cols))
+}
+
+/// A connected undirected graph that looks like a road network: the
+/// vertices of a grid moved around a little, a random spanning tree of the
+/// grid plus about half of its other edges, weighted by their length in
+/// hundredths of the grid spacing.
+pub fn road(rows: usize, cols: usize, seed: u64) -> (AdjacencyList, Vec<(f64, f64)>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let coordinates = grid_coordinates(rows, cols)
+        .into_iter()
+        .map(|(x, y)| (x + rng.gen_range(-0.3..0.3), y + rng.gen_range(-0.3..0.3)))
+        .collect::<Vec<_>>();
+
+    let mut edges = grid_edges(rows, cols);
+    edges.shuffle(&mut rng);
+
+    let mut clusters = UnionFind::new(rows * cols);
+    let mut graph = AdjacencyList::new(rows * cols);
+    for (u, v) in edges {
+        let tree = clusters.find(u) != clusters.find(v);
+        if tree || rng.gen_bool(0.5) {
+            clusters.union(u, v);
+
+            let (dx, dy) = (
+                coordinates[u].0 - coordinates[v].0,
+                coordinates[u].1 - coordinates[v].1,
+            );
+            let c = ((dx * dx + dy * dy).sqrt() * 100.0).round().max(1.0) as usize;
+            graph.adj[u].push((v, c));
+            graph.adj[v].push((u, c));
+        }
+    }
+
+    (graph, coordinates)
+}
+
+/// An undirected graph of two complete halves joined by `cut` random
+/// edges, so its only minimum cut has exactly `cut` edges. Returns the
+/// graph and the side of every vertex.
+pub fn planted_partition(n: usize, cut: usize, seed: u64) -> (AdjacencyList, Vec<bool>) {
+    // cutting a complete half of n / 2 vertices takes at least n / 2 - 1 edges
+    assert!(
+        cut + 1 < n / 2,
+        "a cut of {} is not the minimum for {} vertices",
+        cut,
+        n
+    );
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut vertices = (0..n).collect::<Vec<_>>();
+    vertices.shuffle(&mut rng);
+
+    let mut side = vec![false; n];
+    for &v in vertices[..n / 2].iter() {
+        side[v] = true;
+    }
+
+    let mut graph = AdjacencyList::new(n);
+    for u in 0..n {
+        for v in u + 1..n {
+            if side[u] == side[v] {
+                graph.adj[u].push((v, 1));
+                graph.adj[v].push((u, 1));
+            }
+        }
+    }
+
+    let (left, right) = vertices.split_at(n / 2);
+    let mut crossing = left
+        .iter()
+        .flat_map(|&u| right.iter().map(move |&v| (u, v)))
+        .collect::<Vec<_>>();
+    crossing.shuffle(&mut rng);
+    for &(u, v) in crossing[..cut].iter() {
+        graph.adj[u].push((v, 1));
+        graph.adj[v].push((u, 1));
+    }
+
+    (graph, side)
+}
+
+/// A directed graph with exactly `k` strongly connected components: every
+/// component is a random cycle plus edges inside it with probability `p`,
+/// and edges between components only go from a lower to a higher component
+/// id, also with probability `p`. Returns the graph and the component id of
+/// every vertex.
+pub fn strongly_connected(n: usize, k: usize, p: f64, seed: u64) -> (AdjacencyList, Vec<usize>) {
+    assert!(
+        k > 0 && k <= n,
+        "{} vertices can't have {} components",
+        n,
+        k
+    );
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut vertices = (0..n).collect::<Vec<_>>();
+    vertices.shuffle(&mut rng);
+
+    // the first k vertices make sure no component is empty
+    let mut component = vec![0; n];
+    let mut members = vec![Vec::new(); k];
+    for (i, &v) in vertices.iter().enumerate() {
+        let id = if i < k { i } else { rng.gen_range(0..k) };
+        component[v] = id;
+        members[id].push(v);
+    }
+
+    let mut graph = AdjacencyList::new(n);
+    for cycle in members.iter().filter(|m| m.len() > 1) {
+        for (i, &u) in cycle.iter().enumerate() {
+            graph.adj[u].push((cycle[(i + 1) % cycle.len()], 1));
+        }
+    }
+    for u in 0..n {
+        for v in 0..n {
+            if u != v && component[u] <= component[v] && rng.gen_bool(p) {
+                graph.adj[u].push((v, 1));
+            }
+        }
+    }
+
+    (graph, component)
+}
+
+/// The same graph with random weights between 1 and `max`. Both directions
+/// of an edge get the same weight, so undirected graphs stay undirected.
+pub fn with_random_weights(graph: &AdjacencyList, max: usize, seed: u64) -> AdjacencyList {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut weights = HashMap::new();
+
+    AdjacencyList::new_from_edges(
+        graph.vertex_count(),
+        graph.edges().map(|(u, v, _)| {
+            let c = *weights
+                .entry((usize::min(u, v), usize::max(u, v)))
+                .or_insert_with(|| rng.gen_range(1..=max));
+            (u, v, c)
+        }),
+    )
+}
+
+/// The `u v` lines `new_from_edge_list` in course two reads, vertices
+/// without edges don't show up.
+pub fn to_edge_list<G: Graph>(graph: &G) -> String {
+    let mut out = String::new();
+    for (u, v, _) in graph.edges() {
+        writeln!(out, "{} {}", u + 1, v + 1).unwrap();
+    }
+
+    out
+}
+
+/// The adjacency lists the Karger `Graph` in course one reads, a line per
+/// vertex starting with the vertex.
+pub fn to_adjacency_list<G: Graph>(graph: &G) -> String {
+    let mut out = String::new();
+    for u in graph.vertices() {
+        write!(out, "{}", u + 1).unwrap();
+        for v in graph.neighbours(u) {
+            write!(out, " {}", v + 1).unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    out
+}
+
+/// The `vertex,distance` adjacency lists `read_graph` in course two reads.
+pub fn to_weighted_adjacency_list(graph: &AdjacencyList) -> String {
+    let mut out = String::new();
+    for u in graph.vertices() {
+        write!(out, "{}", u + 1).unwrap();
+        for (v, c) in graph.weighted_neighbours(u) {
+            write!(out, "\t{},{}", v + 1, c).unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    out
+}
+
+fn grid_edges(rows: usize, cols: usize) -> Vec<(usize, usize)> {
+    let mut edges = Vec::new();
+    for r in 0..rows {
+        for c in 0..cols {
+            let u = r * cols + c;
+            if c + 1 < cols {
+                edges.push((u, u + 1));
+            }
+            if r + 1 < rows {
+                edges.push((u, u + cols));
+            }
+        }
+    }
+
+    edges
+}
+
+fn grid_coordinates(rows: usize, cols: usize) -> Vec<(f64, f64)> {
+    (0..rows * cols)
+        .map(|u| ((u % cols) as f64, (u / cols) as f64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bfs, dfs, scc};
+
+    fn is_undirected(graph: &AdjacencyList) -> bool {
+        graph
+            .edges()
+            .all(|(u, v, c)| graph.adj[v].contains(&(u, c)))
+    }
+
+    #[test]
+    fn gnp_test() {
+        let graph = gnp(50, 0.2, false, 1);
+        assert_eq!(graph, gnp(50, 0.2, false, 1));
+        assert_ne!(graph, gnp(50, 0.2, false, 2));
+        assert!(is_undirected(&graph));
+        assert!(graph.edges().all(|(u, v, _)| u != v));
+
+        assert_eq!(gnp(10, 1.0, true, 0).edge_count(), 90);
+        assert_eq!(gnp(10, 0.0, false, 0).edge_count(), 0);
+    }
+
+    #[test]
+    fn dag_test() {
+        let (graph, order) = dag(30, 0.3, 7);
+        assert!(dfs::topological_sort(&graph).is_ok());
+
+        let position = order
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| (v, i))
+            .collect::<HashMap<_, _>>();
+        assert!(graph.edges().all(|(u, v, _)| position[&u] < position[&v]));
+    }
+
+    #[test]
+    fn grid_test() {
+        let (graph, coordinates) = grid(3, 4);
+
+        // (rows - 1) * cols + rows * (cols - 1) edges, both directions
+        assert_eq!(graph.edge_count(), 2 * (2 * 4 + 3 * 3));
+        assert_eq!(coordinates[6], (2.0, 1.0));
+        assert_eq!(bfs::shortest_paths(&graph, 0).distance(11), Some(5));
+    }
+
+    #[test]
+    fn road_test() {
+        let (graph, coordinates) = road(10, 10, 3);
+
+        assert_eq!(coordinates.len(), 100);
+        assert!(is_undirected(&graph));
+        assert_eq!(bfs::connected_components(&graph).len(), 1);
+        // a spanning tree at least and never more than the grid
+        assert!(graph.edge_count() >= 2 * 99 && graph.edge_count() <= 2 * 180);
+    }
+
+    #[test]
+    fn planted_partition_test() {
+        let (graph, side) = planted_partition(20, 3, 5);
+
+        let crossing = graph
+            .edges()
+            .filter(|&(u, v, _)| side[u] != side[v])
+            .count();
+        assert_eq!(crossing, 2 * 3);
+        assert_eq!(side.iter().filter(|&&s| s).count(), 10);
+    }
+
+    #[test]
+    fn strongly_connected_test() {
+        let (graph, component) = strongly_connected(40, 6, 0.05, 9);
+
+        let leaders = scc::kosaraju(&graph);
+        for u in 0..40 {
+            for v in 0..40 {
+                assert_eq!(leaders[u] == leaders[v], component[u] == component[v]);
+            }
+        }
+    }
+
+    #[test]
+    fn with_random_weights_test() {
+        let graph = with_random_weights(&gnp(30, 0.3, false, 4), 10, 4);
+
+        assert!(is_undirected(&graph));
+        assert!(graph.edges().all(|(_, _, c)| (1..=10).contains(&c)));
+    }
+
+    #[test]
+    fn text_test() {
+        let graph = AdjacencyList::new_from_edges(3, [(0, 1, 4), (1, 0, 4), (1, 2, 2)]);
+
+        assert_eq!(to_edge_list(&graph), "1 2\n2 1\n2 3\n");
+        assert_eq!(to_adjacency_list(&graph), "1 2\n2 1 3\n3\n");
+        assert_eq!(
+            to_weighted_adjacency_list(&graph),
+            "1\t2,4\n2\t1,4\t3,2\n3\n"
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use std::ops::Range;
+
+/// A graph over the vertices `0..vertex_count()`.
+///
+/// Edges are directed. An undirected graph is one where every edge shows up
+/// in the neighbours of both of its endpoints, so it is counted twice by
+/// `edge_count` and yielded twice by `edges`.
+///
+/// Unweighted graphs report every edge with a weight of 1, or of `()` if they
+/// have no use for weights at all.
+pub trait Graph {
+    type Weight: Copy;
+
+    fn vertex_count(&self) -> usize;
+
+    fn edge_count(&self) -> usize;
+
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, Self::Weight)>;
+
+    fn neighbours(&self, v: usize) -> impl Iterator<Item = usize> {
+        self.weighted_neighbours(v).map(|(w, _)| w)
+    }
+
+    fn vertices(&self) -> Range<usize> {
+        0..self.vertex_count()
+    }
+
+    /// Every edge as a (tail, head, weight) triple.
+    fn edges(&self) -> impl Iterator<Item = (usize, usize, Self::Weight)> {
+        self.vertices()
+            .flat_map(move |u| self.weighted_neighbours(u).map(move |(v, c)| (u, v, c)))
+    }
+}
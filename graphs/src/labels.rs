@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use crate::{parse::Vertices, Graph};
+
+/// Hands out the dense indices `0..len()` to labels in the order they are
+/// first seen.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SymbolTable {
+    indices: HashMap<String, usize>,
+    labels: Vec<String>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, label: &str) -> usize {
+        if let Some(&i) = self.indices.get(label) {
+            return i;
+        }
+
+        self.indices.insert(label.to_string(), self.labels.len());
+        self.labels.push(label.to_string());
+        self.labels.len() - 1
+    }
+
+    pub fn index(&self, label: &str) -> Option<usize> {
+        self.indices.get(label).copied()
+    }
+
+    pub fn label(&self, v: usize) -> &str {
+        &self.labels[v]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+impl Vertices for SymbolTable {
+    fn expected(&self) -> &'static str {
+        "a vertex label"
+    }
+
+    fn vertex(&mut self, token: &str) -> Option<usize> {
+        Some(self.intern(token))
+    }
+}
+
+/// A graph whose vertices came from arbitrary labels, along with the table
+/// needed to translate results back to them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabeledGraph<G> {
+    pub graph: G,
+    pub symbols: SymbolTable,
+}
+
+impl<G> LabeledGraph<G> {
+    pub fn new(graph: G, symbols: SymbolTable) -> Self {
+        Self { graph, symbols }
+    }
+
+    pub fn index(&self, label: &str) -> Option<usize> {
+        self.symbols.index(label)
+    }
+
+    pub fn label(&self, v: usize) -> &str {
+        self.symbols.label(v)
+    }
+
+    pub fn labels(&self, vertices: impl IntoIterator<Item = usize>) -> Vec<&str> {
+        vertices.into_iter().map(|v| self.label(v)).collect()
+    }
+
+    /// Keys a per vertex result, like a `Vec` indexed by vertex after
+    /// `enumerate` or a `HashMap` from vertices, by label instead.
+    pub fn by_label<T>(&self, values: impl IntoIterator<Item = (usize, T)>) -> HashMap<&str, T> {
+        values
+            .into_iter()
+            .map(|(v, x)| (self.label(v), x))
+            .collect()
+    }
+}
+
+impl<G: Graph> Graph for LabeledGraph<G> {
+    type Weight = G::Weight;
+
+    fn vertex_count(&self) -> usize {
+        self.graph.vertex_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn weighted_neighbours(&self, v: usize) -> impl Iterator<Item = (usize, G::Weight)> {
+        self.graph.weighted_neighbours(v)
+    }
+
+    fn neighbours(&self, v: usize) -> impl Iterator<Item = usize> {
+        self.graph.neighbours(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bfs, AdjacencyList};
+
+    #[test]
+    fn intern_test() {
+        let mut symbols = SymbolTable::new();
+
+        assert_eq!(symbols.intern("db.internal"), 0);
+        assert_eq!(symbols.intern("web-1"), 1);
+        assert_eq!(symbols.intern("db.internal"), 0);
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols.index("web-1"), Some(1));
+        assert_eq!(symbols.index("web-2"), None);
+        assert_eq!(symbols.label(0), "db.internal");
+    }
+
+    #[test]
+    fn labeled_graph_test() {
+        let mut symbols = SymbolTable::new();
+        let edges = [("a", "b"), ("b", "c"), ("a", "d")]
+            .iter()
+            .map(|&(u, v)| (symbols.intern(u), symbols.intern(v), 1))
+            .collect::<Vec<_>>();
+        let graph = LabeledGraph::new(AdjacencyList::new_from_edges(symbols.len(), edges), symbols);
+
+        let distances =
+            graph.by_label(bfs::shortest_paths(&graph, graph.index("a").unwrap()).reached());
+        assert_eq!(
+            distances,
+            HashMap::from([("a", 0), ("b", 1), ("c", 2), ("d", 1)])
+        );
+
+        assert_eq!(graph.labels(graph.neighbours(0)), vec!["b", "d"]);
+    }
+}
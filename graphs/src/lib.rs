@@ -0,0 +1,28 @@
+mod adjacency;
+mod csr;
+mod graph;
+mod labels;
+mod paths;
+
+pub mod bfs;
+pub mod biconnected;
+pub mod dag;
+pub mod dfs;
+pub mod dijkstra;
+pub mod dot;
+pub mod euler;
+pub mod formats;
+pub mod generate;
+pub mod mst;
+pub mod parse;
+pub mod priority_queue;
+pub mod scc;
+pub mod union_find;
+pub mod weight;
+
+pub use adjacency::AdjacencyList;
+pub use csr::CsrGraph;
+pub use graph::Graph;
+pub use labels::{LabeledGraph, SymbolTable};
+pub use paths::Paths;
+pub use weight::Weight;
@@ -0,0 +1,134 @@
+use crate::{
+    priority_queue::{IndexedPriorityQueue, PriorityQueue},
+    union_find::UnionFind,
+    Graph, Weight,
+};
+
+/// Returns the cost of a minimum spanning tree of an undirected graph.
+pub fn prims_algorithm<G>(graph: &G, start: usize) -> G::Weight
+where
+    G: Graph,
+    G::Weight: Weight,
+{
+    cost(&prims_tree(graph, start))
+}
+
+/// Returns the (parent, vertex, cost) edges of a minimum spanning tree of an
+/// undirected graph, in the order Prim's algorithm picks them.
+pub fn prims_tree<G>(graph: &G, start: usize) -> Vec<(usize, usize, G::Weight)>
+where
+    G: Graph,
+    G::Weight: Weight,
+{
+    let mut in_tree = vec![false; graph.vertex_count()];
+    let mut parents = vec![start; graph.vertex_count()];
+    // every vertex next to the tree, keyed by its cheapest edge into it
+    let mut heap: IndexedPriorityQueue<G::Weight> = IndexedPriorityQueue::new(graph.vertex_count());
+
+    let mut tree = Vec::new();
+    in_tree[start] = true;
+    let mut next = Some((start, G::Weight::ZERO));
+
+    while let Some((v, k)) = next {
+        if v != start {
+            tree.push((parents[v], v, k));
+            in_tree[v] = true;
+        }
+
+        for (w, c) in graph.weighted_neighbours(v) {
+            if !in_tree[w] && heap.push_or_decrease(w, c) {
+                parents[w] = v;
+            }
+        }
+
+        next = heap.pop_min();
+    }
+
+    tree
+}
+
+/// Returns the cost of a minimum spanning tree of an undirected graph.
+pub fn kruskals_algorithm<G>(graph: &G) -> G::Weight
+where
+    G: Graph,
+    G::Weight: Weight,
+{
+    cost(&kruskals_tree(graph))
+}
+
+/// Returns the edges of a minimum spanning tree of an undirected graph, in
+/// the order Kruskal's algorithm picks them.
+pub fn kruskals_tree<G>(graph: &G) -> Vec<(usize, usize, G::Weight)>
+where
+    G: Graph,
+    G::Weight: Weight,
+{
+    let mut uf = UnionFind::new(graph.vertex_count());
+
+    let mut edges = graph.edges().collect::<Vec<_>>();
+    edges.sort_by_key(|&(_, _, c)| c);
+
+    let mut tree = Vec::new();
+    for (a, b, c) in edges {
+        if uf.find(a) == uf.find(b) {
+            // cycle
+            continue;
+        }
+
+        tree.push((a, b, c));
+        uf.union(a, b);
+    }
+
+    tree
+}
+
+fn cost<W: Weight>(tree: &[(usize, usize, W)]) -> W {
+    tree.iter()
+        .fold(W::ZERO, |total, &(_, _, c)| total.saturating_add(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{weight::OrderedFloat, AdjacencyList};
+
+    const SMALL: [(usize, usize, usize); 5] =
+        [(0, 1, 1), (0, 2, 4), (0, 3, 3), (1, 3, 2), (2, 3, 5)];
+
+    #[test]
+    fn prims_test() {
+        let graph = AdjacencyList::new_from_edges(
+            4,
+            SMALL
+                .iter()
+                .flat_map(|&(u, v, c)| [(u, v, c as isize), (v, u, c as isize)]),
+        );
+
+        assert_eq!(prims_algorithm(&graph, 0), 7);
+        assert_eq!(prims_algorithm(&graph, 2), 7);
+        assert_eq!(prims_tree(&graph, 0), vec![(0, 1, 1), (1, 3, 2), (0, 2, 4)]);
+    }
+
+    #[test]
+    fn kruskals_test() {
+        let graph = AdjacencyList::new_from_edges(4, SMALL);
+
+        assert_eq!(kruskals_algorithm(&graph), 7);
+        assert_eq!(kruskals_tree(&graph), vec![(0, 1, 1), (1, 3, 2), (0, 2, 4)]);
+    }
+
+    #[test]
+    fn float_test() {
+        let graph = AdjacencyList::new_from_edges(
+            4,
+            SMALL
+                .iter()
+                .map(|&(u, v, c)| (u, v, OrderedFloat(c as f32 / 4.0)))
+                .flat_map(|(u, v, c)| [(u, v, c), (v, u, c)]),
+        );
+
+        assert_eq!(prims_algorithm(&graph, 3), OrderedFloat(1.75));
+        assert_eq!(kruskals_algorithm(&graph), OrderedFloat(1.75));
+        assert_eq!(kruskals_tree(&graph).len(), 3);
+    }
+}
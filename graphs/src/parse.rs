@@ -0,0 +1,267 @@
+use std::{
+    error::Error,
+    fmt,
+    iter::Enumerate,
+    str::{FromStr, SplitWhitespace},
+};
+
+/// A malformed line of some input, along with what the parser expected to
+/// find there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,  // set by `in_file` once the caller knows it
+    pub line: usize,           // 1-indexed
+    pub expected: String,      // what the parser was looking for
+    pub found: Option<String>, // the offending token, `None` if the line or input ran out
+}
+
+impl ParseError {
+    pub fn new(line: usize, expected: &str, found: Option<&str>) -> Self {
+        Self {
+            file: None,
+            line,
+            expected: expected.to_string(),
+            found: found.map(|x| x.to_string()),
+        }
+    }
+
+    pub fn in_file(self, file: &str) -> Self {
+        Self {
+            file: Some(file.to_string()),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}: expected {}, found ", self.line, self.expected)?;
+
+        match &self.found {
+            Some(token) => write!(f, "`{}`", token),
+            None => write!(f, "nothing"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// The non-blank lines of an input, numbered from 1.
+pub struct Lines<'a> {
+    iter: Enumerate<std::str::Lines<'a>>,
+    last: usize,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            iter: input.lines().enumerate(),
+            last: 0,
+        }
+    }
+
+    /// Like `next`, but running out of input is an error.
+    pub fn expect_line(&mut self, expected: &str) -> Result<Tokens<'a>, ParseError> {
+        let last = self.last;
+        self.next()
+            .ok_or_else(|| ParseError::new(last + 1, expected, None))
+    }
+
+    /// Checks that no line is left.
+    pub fn expect_end(&mut self) -> Result<(), ParseError> {
+        match self.next() {
+            Some(mut line) => {
+                let token = line.next_str("the end of the input")?;
+                Err(line.error("the end of the input", Some(token)))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Tokens<'a>;
+
+    fn next(&mut self) -> Option<Tokens<'a>> {
+        for (i, line) in self.iter.by_ref() {
+            self.last = i + 1;
+            if !line.trim().is_empty() {
+                return Some(Tokens::new(i + 1, line));
+            }
+        }
+
+        None
+    }
+}
+
+/// The whitespace separated tokens of a single line.
+pub struct Tokens<'a> {
+    line: usize,
+    split: SplitWhitespace<'a>,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn new(line: usize, text: &'a str) -> Self {
+        Self {
+            line,
+            split: text.split_whitespace(),
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Whether every token of the line has been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.split.clone().next().is_none()
+    }
+
+    /// Checks that every token of the line has been consumed.
+    pub fn expect_end(&mut self) -> Result<(), ParseError> {
+        match self.split.next() {
+            Some(token) => Err(self.error("the end of the line", Some(token))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn error(&self, expected: &str, found: Option<&str>) -> ParseError {
+        ParseError::new(self.line, expected, found)
+    }
+
+    pub fn next_str(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        self.split.next().ok_or_else(|| self.error(expected, None))
+    }
+
+    pub fn next<T: FromStr>(&mut self, expected: &str) -> Result<T, ParseError> {
+        let token = self.next_str(expected)?;
+        token.parse().map_err(|_| self.error(expected, Some(token)))
+    }
+
+    /// Parses a 1-indexed vertex and returns it 0-indexed.
+    pub fn vertex(&mut self) -> Result<usize, ParseError> {
+        self.vertex_with(&mut Indexed)
+    }
+
+    pub fn vertex_with<V: Vertices>(&mut self, vertices: &mut V) -> Result<usize, ParseError> {
+        let token = self.next_str(vertices.expected())?;
+        vertices
+            .vertex(token)
+            .ok_or_else(|| self.error(vertices.expected(), Some(token)))
+    }
+
+    /// Same as `vertex` but the vertex should also be one of the first `n`.
+    pub fn vertex_below(&mut self, n: usize) -> Result<usize, ParseError> {
+        let token = self.next_str("a vertex")?;
+        match token.parse::<usize>() {
+            Ok(v) if v > 0 && v <= n => Ok(v - 1),
+            _ => Err(self.error(&format!("a vertex between 1 and {}", n), Some(token))),
+        }
+    }
+}
+
+/// How a parser turns a token into a dense 0-indexed vertex.
+pub trait Vertices {
+    /// What a vertex token should look like, for error messages.
+    fn expected(&self) -> &'static str;
+
+    fn vertex(&mut self, token: &str) -> Option<usize>;
+}
+
+/// Vertices written as 1-indexed integers, the way every course input is.
+pub struct Indexed;
+
+impl Vertices for Indexed {
+    fn expected(&self) -> &'static str {
+        "a 1-indexed vertex"
+    }
+
+    fn vertex(&mut self, token: &str) -> Option<usize> {
+        token.parse::<usize>().ok()?.checked_sub(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_test() {
+        let mut lines = Lines::new("3 4\n\n1 x\n0 7");
+
+        let mut header = lines.expect_line("a header").unwrap();
+        assert_eq!(header.next::<usize>("n"), Ok(3));
+        assert_eq!(
+            header.vertex_below(3),
+            Err(ParseError::new(1, "a vertex between 1 and 3", Some("4")))
+        );
+        assert_eq!(
+            header.next::<usize>("m"),
+            Err(ParseError::new(1, "m", None))
+        );
+
+        // blank lines are skipped but still counted
+        let mut second = lines.next().unwrap();
+        assert_eq!(second.line(), 3);
+        assert_eq!(second.vertex(), Ok(0));
+        assert_eq!(
+            second.next::<isize>("a cost"),
+            Err(ParseError::new(3, "a cost", Some("x")))
+        );
+
+        let mut third = lines.next().unwrap();
+        assert_eq!(
+            third.vertex(),
+            Err(ParseError::new(4, "a 1-indexed vertex", Some("0")))
+        );
+
+        assert_eq!(
+            lines.expect_line("an edge").err(),
+            Some(ParseError::new(5, "an edge", None))
+        );
+    }
+
+    #[test]
+    fn end_test() {
+        let mut lines = Lines::new(
+            "1 2
+3 4 5
+
+6",
+        );
+
+        let mut first = lines.next().unwrap();
+        first.next::<usize>("a").unwrap();
+        first.next::<usize>("b").unwrap();
+        assert_eq!(first.expect_end(), Ok(()));
+
+        let mut second = lines.next().unwrap();
+        second.next::<usize>("a").unwrap();
+        second.next::<usize>("b").unwrap();
+        assert_eq!(
+            second.expect_end(),
+            Err(ParseError::new(2, "the end of the line", Some("5")))
+        );
+
+        assert_eq!(
+            lines.expect_end(),
+            Err(ParseError::new(4, "the end of the input", Some("6")))
+        );
+        assert_eq!(lines.expect_end(), Ok(()));
+    }
+
+    #[test]
+    fn display_test() {
+        let error = ParseError::new(12, "a cost", Some("x")).in_file("data/edges.txt");
+        assert_eq!(
+            error.to_string(),
+            "data/edges.txt:12: expected a cost, found `x`"
+        );
+
+        let error = ParseError::new(3, "a vertex", None);
+        assert_eq!(error.to_string(), "3: expected a vertex, found nothing");
+    }
+}
@@ -0,0 +1,55 @@
+use crate::Weight;
+
+/// The distance and parent of every vertex on the best paths from one or
+/// more starts, `None` for vertices that weren't reached.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paths<W = usize> {
+    pub distances: Vec<Option<W>>,
+    pub parents: Vec<Option<usize>>,
+}
+
+impl<W: Weight> Paths<W> {
+    /// Nothing reached yet but `starts`, at distance zero.
+    pub fn new(n: usize, starts: &[usize]) -> Self {
+        let mut paths = Self {
+            distances: vec![None; n],
+            parents: vec![None; n],
+        };
+        for &start in starts {
+            paths.distances[start] = Some(W::ZERO);
+        }
+
+        paths
+    }
+
+    pub fn distance(&self, v: usize) -> Option<W> {
+        self.distances[v]
+    }
+
+    /// The start the path to `v` comes from, the nearest one when searching
+    /// from several.
+    pub fn source(&self, v: usize) -> Option<usize> {
+        self.path_to(v).map(|path| path[0])
+    }
+
+    /// The vertices on the path from the start to `v`, both included.
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        self.distances[v]?;
+
+        let mut path = vec![v];
+        while let Some(parent) = self.parents[*path.last().unwrap()] {
+            path.push(parent);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// The (vertex, distance) pairs of every vertex reached.
+    pub fn reached(&self) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.distances
+            .iter()
+            .enumerate()
+            .filter_map(|(v, d)| d.map(|d| (v, d)))
+    }
+}
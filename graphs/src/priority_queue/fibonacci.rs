@@ -0,0 +1,256 @@
+use super::PriorityQueue;
+
+#[derive(Debug, Clone)]
+struct Node<K> {
+    key: Option<K>,        // `None` while the item is not queued
+    parent: Option<usize>, // `None` for a root
+    child: Option<usize>,  // any one of the children
+    left: usize,           // the previous sibling on a circular list
+    right: usize,          // the next sibling on a circular list
+    degree: usize,         // the number of children
+    marked: bool,          // whether it lost a child since it became a child
+}
+
+/// A Fibonacci heap over the items `0..n`, each item being its own node so
+/// that it can be found to decrease its key. Pushing, melding and decreasing a
+/// key take constant amortised time, and popping takes logarithmic amortised
+/// time.
+#[derive(Debug, Clone)]
+pub struct FibonacciHeap<K> {
+    nodes: Vec<Node<K>>,
+    min: Option<usize>, // the root with the smallest key, on the list of roots
+    len: usize,
+}
+
+impl<K: Ord + Copy> PriorityQueue<K> for FibonacciHeap<K> {
+    fn new(n: usize) -> Self {
+        Self {
+            nodes: (0..n).map(Self::node).collect(),
+            min: None,
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn key(&self, item: usize) -> Option<K> {
+        self.nodes[item].key
+    }
+
+    fn push(&mut self, item: usize, key: K) {
+        assert!(!self.contains(item), "item {} is already queued", item);
+
+        self.nodes[item] = Node {
+            key: Some(key),
+            ..Self::node(item)
+        };
+        self.add_root(item);
+        self.len += 1;
+    }
+
+    fn pop_min(&mut self) -> Option<(usize, K)> {
+        let min = self.min?;
+
+        // every child of the minimum becomes a root
+        if let Some(child) = self.nodes[min].child.take() {
+            let mut node = child;
+            loop {
+                self.nodes[node].parent = None;
+                self.nodes[node].marked = false;
+                node = self.nodes[node].right;
+                if node == child {
+                    break;
+                }
+            }
+            self.splice(min, child);
+        }
+
+        let next = self.nodes[min].right;
+        self.unlink(min);
+        self.nodes[min].degree = 0;
+        self.min = (next != min).then_some(next);
+        self.consolidate();
+        self.len -= 1;
+
+        Some((min, self.nodes[min].key.take().unwrap()))
+    }
+
+    fn decrease_key(&mut self, item: usize, key: K) {
+        let old = self.nodes[item]
+            .key
+            .unwrap_or_else(|| panic!("item {} is not queued", item));
+        assert!(key <= old, "the key of item {} can only decrease", item);
+
+        self.nodes[item].key = Some(key);
+        if let Some(parent) = self.nodes[item].parent {
+            if self.nodes[item].key < self.nodes[parent].key {
+                self.cut(item);
+                self.cascading_cut(parent);
+            }
+        }
+
+        if self.nodes[item].key < self.nodes[self.min.unwrap()].key {
+            self.min = Some(item);
+        }
+    }
+}
+
+impl<K: Ord + Copy> FibonacciHeap<K> {
+    /// Moves every item of `other` into this heap. Both heaps must be over the
+    /// same `0..n` and share no items. Splicing the two lists of roots takes
+    /// constant time, though moving the nodes of `other` over is linear in `n`.
+    pub fn meld(&mut self, other: Self) {
+        assert_eq!(
+            self.nodes.len(),
+            other.nodes.len(),
+            "heaps over different items"
+        );
+
+        for (item, node) in other.nodes.into_iter().enumerate() {
+            if node.key.is_some() {
+                assert!(!self.contains(item), "item {} is in both heaps", item);
+                self.nodes[item] = node;
+            }
+        }
+
+        if let Some(other_min) = other.min {
+            match self.min {
+                Some(min) => {
+                    self.splice(min, other_min);
+                    if self.nodes[other_min].key < self.nodes[min].key {
+                        self.min = Some(other_min);
+                    }
+                }
+                None => self.min = Some(other_min),
+            }
+        }
+        self.len += other.len;
+    }
+
+    /// An item that is not queued, alone on its circular list.
+    fn node(item: usize) -> Node<K> {
+        Node {
+            key: None,
+            parent: None,
+            child: None,
+            left: item,
+            right: item,
+            degree: 0,
+            marked: false,
+        }
+    }
+
+    fn add_root(&mut self, node: usize) {
+        match self.min {
+            Some(min) => {
+                self.splice(min, node);
+                if self.nodes[node].key < self.nodes[min].key {
+                    self.min = Some(node);
+                }
+            }
+            None => self.min = Some(node),
+        }
+    }
+
+    /// Joins the circular lists holding `a` and `b` into one.
+    fn splice(&mut self, a: usize, b: usize) {
+        let a_right = self.nodes[a].right;
+        let b_left = self.nodes[b].left;
+
+        self.nodes[a].right = b;
+        self.nodes[b].left = a;
+        self.nodes[b_left].right = a_right;
+        self.nodes[a_right].left = b_left;
+    }
+
+    /// Takes `node` off its circular list, leaving it alone on its own.
+    fn unlink(&mut self, node: usize) {
+        let Node { left, right, .. } = self.nodes[node];
+
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+        self.nodes[node].left = node;
+        self.nodes[node].right = node;
+    }
+
+    /// Links roots of the same degree until no two roots share a degree.
+    fn consolidate(&mut self) {
+        let Some(min) = self.min else {
+            return;
+        };
+
+        let mut roots = vec![min];
+        let mut node = self.nodes[min].right;
+        while node != min {
+            roots.push(node);
+            node = self.nodes[node].right;
+        }
+
+        let mut by_degree: Vec<Option<usize>> = Vec::new();
+        for mut root in roots {
+            let mut degree = self.nodes[root].degree;
+            while let Some(other) = by_degree.get_mut(degree).and_then(Option::take) {
+                let (parent, child) = if self.nodes[other].key < self.nodes[root].key {
+                    (other, root)
+                } else {
+                    (root, other)
+                };
+                self.make_child(child, parent);
+                root = parent;
+                degree += 1;
+            }
+
+            if by_degree.len() <= degree {
+                by_degree.resize(degree + 1, None);
+            }
+            by_degree[degree] = Some(root);
+        }
+
+        self.min = by_degree
+            .into_iter()
+            .flatten()
+            .min_by_key(|&root| self.nodes[root].key);
+    }
+
+    fn make_child(&mut self, child: usize, parent: usize) {
+        self.unlink(child);
+        self.nodes[child].parent = Some(parent);
+        self.nodes[child].marked = false;
+
+        match self.nodes[parent].child {
+            Some(first) => self.splice(first, child),
+            None => self.nodes[parent].child = Some(child),
+        }
+        self.nodes[parent].degree += 1;
+    }
+
+    /// Moves `node` from under its parent to the list of roots.
+    fn cut(&mut self, node: usize) {
+        let parent = self.nodes[node].parent.take().unwrap();
+
+        if self.nodes[parent].child == Some(node) {
+            let right = self.nodes[node].right;
+            self.nodes[parent].child = (right != node).then_some(right);
+        }
+        self.unlink(node);
+        self.nodes[parent].degree -= 1;
+        self.nodes[node].marked = false;
+
+        self.add_root(node);
+    }
+
+    /// Cuts the ancestors of a node that just lost a child for as long as
+    /// they had already lost one before.
+    fn cascading_cut(&mut self, mut node: usize) {
+        while let Some(parent) = self.nodes[node].parent {
+            if !self.nodes[node].marked {
+                self.nodes[node].marked = true;
+                break;
+            }
+            self.cut(node);
+            node = parent;
+        }
+    }
+}
@@ -0,0 +1,249 @@
+mod fibonacci;
+mod pairing;
+
+pub use fibonacci::FibonacciHeap;
+pub use pairing::PairingHeap;
+
+/// A min-priority queue of the items `0..n`, each with a key that can be
+/// decreased while the item is queued. Dijkstra and Prim run on any of them.
+pub trait PriorityQueue<K: Ord + Copy> {
+    fn new(n: usize) -> Self;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The key of `item`, or `None` if it is not queued.
+    fn key(&self, item: usize) -> Option<K>;
+
+    fn contains(&self, item: usize) -> bool {
+        self.key(item).is_some()
+    }
+
+    fn push(&mut self, item: usize, key: K);
+
+    /// Removes the item with the smallest key.
+    fn pop_min(&mut self) -> Option<(usize, K)>;
+
+    fn decrease_key(&mut self, item: usize, key: K);
+
+    /// Queues `item` with `key`, or lowers its key to `key` if it is queued
+    /// with a larger one. Returns whether anything changed.
+    fn push_or_decrease(&mut self, item: usize, key: K) -> bool {
+        match self.key(item) {
+            None => self.push(item, key),
+            Some(old) if key < old => self.decrease_key(item, key),
+            Some(_) => return false,
+        }
+
+        true
+    }
+}
+
+/// A min-priority queue on a d-ary heap that keeps track of where every item
+/// sits so that its key can be decreased in place.
+#[derive(Debug, Clone)]
+pub struct IndexedPriorityQueue<K, const D: usize = 4> {
+    heap: Vec<usize>,      // the items in heap order
+    keys: Vec<Option<K>>,  // the key of every item in the queue
+    positions: Vec<usize>, // where every item in the queue sits in `heap`
+}
+
+impl<K: Ord + Copy, const D: usize> PriorityQueue<K> for IndexedPriorityQueue<K, D> {
+    fn new(n: usize) -> Self {
+        assert!(D >= 2, "a {}-ary heap", D);
+
+        Self {
+            heap: Vec::new(),
+            keys: vec![None; n],
+            positions: vec![0; n],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn key(&self, item: usize) -> Option<K> {
+        self.keys[item]
+    }
+
+    fn push(&mut self, item: usize, key: K) {
+        assert!(!self.contains(item), "item {} is already queued", item);
+
+        self.keys[item] = Some(key);
+        self.positions[item] = self.heap.len();
+        self.heap.push(item);
+        self.sift_up(self.heap.len() - 1);
+    }
+
+    fn pop_min(&mut self) -> Option<(usize, K)> {
+        let top = *self.heap.first()?;
+
+        let last = self.heap.pop().unwrap();
+        if !self.heap.is_empty() {
+            self.heap[0] = last;
+            self.positions[last] = 0;
+            self.sift_down(0);
+        }
+
+        Some((top, self.keys[top].take().unwrap()))
+    }
+
+    fn decrease_key(&mut self, item: usize, key: K) {
+        let old = self.keys[item].unwrap_or_else(|| panic!("item {} is not queued", item));
+        assert!(key <= old, "the key of item {} can only decrease", item);
+
+        self.keys[item] = Some(key);
+        self.sift_up(self.positions[item]);
+    }
+}
+
+impl<K: Ord + Copy, const D: usize> IndexedPriorityQueue<K, D> {
+    fn key_at(&self, i: usize) -> K {
+        self.keys[self.heap[i]].unwrap()
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.positions[self.heap[i]] = i;
+        self.positions[self.heap[j]] = j;
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / D;
+            if self.key_at(parent) <= self.key_at(i) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let first = D * i + 1;
+            let Some(smallest) =
+                (first..(first + D).min(self.heap.len())).min_by_key(|&c| self.key_at(c))
+            else {
+                break;
+            };
+            if self.key_at(i) <= self.key_at(smallest) {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn check_queue<Q: PriorityQueue<i32>>() {
+        let mut queue = Q::new(6);
+        for (item, key) in [(0, 5), (1, 3), (2, 8), (3, 1), (4, 9)] {
+            queue.push(item, key);
+        }
+
+        assert!(queue.contains(2) && !queue.contains(5));
+        assert_eq!(queue.pop_min(), Some((3, 1)));
+
+        queue.decrease_key(4, 2);
+        assert!(queue.push_or_decrease(2, 4));
+        assert!(!queue.push_or_decrease(0, 7));
+        assert!(queue.push_or_decrease(5, 6));
+
+        let mut popped = Vec::new();
+        while let Some(entry) = queue.pop_min() {
+            popped.push(entry);
+        }
+        assert_eq!(popped, vec![(4, 2), (1, 3), (2, 4), (0, 5), (5, 6)]);
+        assert!(queue.is_empty() && !queue.contains(4));
+    }
+
+    fn check_random<Q: PriorityQueue<u32>>(seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut queue = Q::new(500);
+        let mut keys = vec![None; 500];
+
+        for _ in 0..5000 {
+            let item = rng.gen_range(0..500);
+            match keys[item] {
+                None => {
+                    let key = rng.gen_range(0..1000);
+                    queue.push(item, key);
+                    keys[item] = Some(key);
+                }
+                Some(key) if key > 0 && rng.gen_bool(0.7) => {
+                    let key = rng.gen_range(0..key);
+                    queue.decrease_key(item, key);
+                    keys[item] = Some(key);
+                }
+                Some(_) => {
+                    let (popped, key) = queue.pop_min().unwrap();
+                    assert_eq!(keys[popped], Some(key));
+                    assert!(keys.iter().flatten().all(|&k| k >= key));
+                    keys[popped] = None;
+                }
+            }
+            assert_eq!(queue.len(), keys.iter().flatten().count());
+        }
+    }
+
+    #[test]
+    fn queue_test() {
+        check_queue::<IndexedPriorityQueue<i32>>();
+        check_queue::<PairingHeap<i32>>();
+        check_queue::<FibonacciHeap<i32>>();
+    }
+
+    #[test]
+    fn random_test() {
+        check_random::<IndexedPriorityQueue<u32, 3>>(2);
+        check_random::<PairingHeap<u32>>(3);
+        check_random::<FibonacciHeap<u32>>(4);
+    }
+
+    #[test]
+    fn meld_test() {
+        let mut pairing: PairingHeap<i32> = PairingHeap::new(8);
+        let mut fibonacci: FibonacciHeap<i32> = FibonacciHeap::new(8);
+        let mut other_pairing: PairingHeap<i32> = PairingHeap::new(8);
+        let mut other_fibonacci: FibonacciHeap<i32> = FibonacciHeap::new(8);
+        for (item, key) in [(0, 4), (2, 7), (4, 1), (6, 9)] {
+            pairing.push(item, key);
+            fibonacci.push(item, key);
+        }
+        for (item, key) in [(1, 3), (3, 8), (5, 0), (7, 6)] {
+            other_pairing.push(item, key);
+            other_fibonacci.push(item, key);
+        }
+
+        // give both sides some structure before melding
+        assert_eq!(pairing.pop_min(), Some((4, 1)));
+        assert_eq!(fibonacci.pop_min(), Some((4, 1)));
+        other_pairing.decrease_key(3, 2);
+        other_fibonacci.decrease_key(3, 2);
+
+        pairing.meld(other_pairing);
+        fibonacci.meld(other_fibonacci);
+        fibonacci.decrease_key(6, 5);
+        pairing.decrease_key(6, 5);
+
+        let expected = vec![(5, 0), (3, 2), (1, 3), (0, 4), (6, 5), (7, 6), (2, 7)];
+        assert_eq!(
+            std::iter::from_fn(|| pairing.pop_min()).collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(
+            std::iter::from_fn(|| fibonacci.pop_min()).collect::<Vec<_>>(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use super::PriorityQueue;
+
+#[derive(Debug, Clone)]
+struct Node<K> {
+    key: Option<K>,         // `None` while the item is not queued
+    child: Option<usize>,   // the first child
+    sibling: Option<usize>, // the next sibling
+    prev: Option<usize>,    // the previous sibling, or the parent of a first child
+}
+
+/// A pairing heap over the items `0..n`, each item being its own node so that
+/// it can be found to decrease its key. Pushing, melding and decreasing a key
+/// link two trees in constant time, and popping pairs up the children of the
+/// root in two passes.
+#[derive(Debug, Clone)]
+pub struct PairingHeap<K> {
+    nodes: Vec<Node<K>>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<K: Ord + Copy> PriorityQueue<K> for PairingHeap<K> {
+    fn new(n: usize) -> Self {
+        let node = Node {
+            key: None,
+            child: None,
+            sibling: None,
+            prev: None,
+        };
+
+        Self {
+            nodes: vec![node; n],
+            root: None,
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn key(&self, item: usize) -> Option<K> {
+        self.nodes[item].key
+    }
+
+    fn push(&mut self, item: usize, key: K) {
+        assert!(!self.contains(item), "item {} is already queued", item);
+
+        self.nodes[item] = Node {
+            key: Some(key),
+            child: None,
+            sibling: None,
+            prev: None,
+        };
+        self.root = Some(self.meld_roots(self.root, item));
+        self.len += 1;
+    }
+
+    fn pop_min(&mut self) -> Option<(usize, K)> {
+        let root = self.root?;
+
+        // detach the children of the root and pair them up left to right
+        let mut paired = Vec::new();
+        let mut next = self.nodes[root].child.take();
+        while let Some(first) = next {
+            let second = self.detach_first(first);
+            next = second.and_then(|second| self.detach_first(second));
+            paired.push(match second {
+                Some(second) => self.link(first, second),
+                None => first,
+            });
+        }
+
+        // then fold the pairs into one tree right to left
+        self.root = paired
+            .into_iter()
+            .rev()
+            .reduce(|tree, pair| self.link(pair, tree));
+        self.len -= 1;
+
+        Some((root, self.nodes[root].key.take().unwrap()))
+    }
+
+    fn decrease_key(&mut self, item: usize, key: K) {
+        let old = self.nodes[item]
+            .key
+            .unwrap_or_else(|| panic!("item {} is not queued", item));
+        assert!(key <= old, "the key of item {} can only decrease", item);
+
+        self.nodes[item].key = Some(key);
+        if self.root == Some(item) {
+            return;
+        }
+
+        // cut the subtree of `item` out and link it back in at the root
+        let prev = self.nodes[item].prev.take().unwrap();
+        let sibling = self.nodes[item].sibling.take();
+        if self.nodes[prev].child == Some(item) {
+            self.nodes[prev].child = sibling;
+        } else {
+            self.nodes[prev].sibling = sibling;
+        }
+        if let Some(sibling) = sibling {
+            self.nodes[sibling].prev = Some(prev);
+        }
+
+        self.root = Some(self.meld_roots(self.root, item));
+    }
+}
+
+impl<K: Ord + Copy> PairingHeap<K> {
+    /// Moves every item of `other` into this heap. Both heaps must be over the
+    /// same `0..n` and share no items. Linking the two trees takes constant
+    /// time, though moving the nodes of `other` over is linear in `n`.
+    pub fn meld(&mut self, other: Self) {
+        assert_eq!(
+            self.nodes.len(),
+            other.nodes.len(),
+            "heaps over different items"
+        );
+
+        for (item, node) in other.nodes.into_iter().enumerate() {
+            if node.key.is_some() {
+                assert!(!self.contains(item), "item {} is in both heaps", item);
+                self.nodes[item] = node;
+            }
+        }
+
+        if let Some(root) = other.root {
+            self.root = Some(self.meld_roots(self.root, root));
+        }
+        self.len += other.len;
+    }
+
+    fn meld_roots(&mut self, root: Option<usize>, tree: usize) -> usize {
+        match root {
+            Some(root) => self.link(root, tree),
+            None => tree,
+        }
+    }
+
+    /// Makes the root with the larger key the first child of the other and
+    /// returns the new root.
+    fn link(&mut self, a: usize, b: usize) -> usize {
+        let (parent, child) = if self.nodes[b].key < self.nodes[a].key {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        let first = self.nodes[parent].child;
+        if let Some(first) = first {
+            self.nodes[first].prev = Some(child);
+        }
+        self.nodes[child].sibling = first;
+        self.nodes[child].prev = Some(parent);
+        self.nodes[parent].child = Some(child);
+
+        parent
+    }
+
+    /// Turns `node` into a root and returns its old next sibling.
+    fn detach_first(&mut self, node: usize) -> Option<usize> {
+        self.nodes[node].prev = None;
+        let sibling = self.nodes[node].sibling.take();
+        if let Some(sibling) = sibling {
+            self.nodes[sibling].prev = None;
+        }
+
+        sibling
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{
+    dfs::{depth_first, Visitor},
+    AdjacencyList, CsrGraph, Graph,
+};
+
+/// Records the order vertices finish in.
+struct FinishOrder(Vec<usize>);
+
+impl Visitor for FinishOrder {
+    fn finish(&mut self, vertex: usize, _time: usize) {
+        self.0.push(vertex);
+    }
+}
+
+/// Gives every vertex the root of the tree that discovered it.
+struct Leaders {
+    current_leader: usize,
+    leaders: Vec<usize>,
+}
+
+impl Visitor for Leaders {
+    fn start(&mut self, root: usize) {
+        self.current_leader = root;
+    }
+
+    fn discover(&mut self, vertex: usize, _time: usize) {
+        self.leaders[vertex] = self.current_leader;
+    }
+}
+
+/// Returns the leader of the strongly connected component of every vertex.
+pub fn kosaraju<G: Graph>(graph: &G) -> Vec<usize> {
+    let reverse = CsrGraph::reversed(graph);
+    let n = graph.vertex_count();
+
+    // the first pass finds the finishing order on the reversed graph
+    let mut ordering = FinishOrder(Vec::with_capacity(n));
+    depth_first(&reverse, (0..n).rev(), &mut ordering);
+
+    // and the second pass discovers a component from each root in reverse
+    // finishing order
+    let mut leaders = Leaders {
+        current_leader: 0,
+        leaders: vec![0; n],
+    };
+    depth_first(graph, ordering.0.into_iter().rev(), &mut leaders);
+
+    leaders.leaders
+}
+
+/// Tarjan's lowlinks: a vertex whose subtree can't reach anything
+/// discovered before it that is still on the stack is the first vertex of
+/// its component, and the component is everything above it on the stack.
+struct Tarjan {
+    discovered: Vec<usize>,
+    low: Vec<usize>,
+    parents: Vec<Option<usize>>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    components: Vec<usize>,
+    count: usize,
+}
+
+impl Tarjan {
+    fn reach(&mut self, tail: usize, head: usize) {
+        if self.on_stack[head] {
+            self.low[tail] = self.low[tail].min(self.discovered[head]);
+        }
+    }
+}
+
+impl Visitor for Tarjan {
+    fn discover(&mut self, vertex: usize, time: usize) {
+        self.discovered[vertex] = time;
+        self.low[vertex] = time;
+        self.on_stack[vertex] = true;
+        self.stack.push(vertex);
+    }
+
+    fn tree_edge(&mut self, tail: usize, head: usize) {
+        self.parents[head] = Some(tail);
+    }
+
+    fn back_edge(&mut self, tail: usize, head: usize) {
+        self.reach(tail, head);
+    }
+
+    fn forward_edge(&mut self, tail: usize, head: usize) {
+        self.reach(tail, head);
+    }
+
+    fn cross_edge(&mut self, tail: usize, head: usize) {
+        self.reach(tail, head);
+    }
+
+    fn finish(&mut self, vertex: usize, _time: usize) {
+        if let Some(parent) = self.parents[vertex] {
+            self.low[parent] = self.low[parent].min(self.low[vertex]);
+        }
+
+        if self.low[vertex] == self.discovered[vertex] {
+            loop {
+                let member = self.stack.pop().unwrap();
+                self.on_stack[member] = false;
+                self.components[member] = self.count;
+                if member == vertex {
+                    break;
+                }
+            }
+            self.count += 1;
+        }
+    }
+}
+
+/// Tarjan's single pass algorithm. Returns the component id of every
+/// vertex, numbered in topological order of the components, so every edge
+/// between two components goes from a lower to a higher id.
+pub fn tarjan<G: Graph>(graph: &G) -> Vec<usize> {
+    let n = graph.vertex_count();
+    let mut tarjan = Tarjan {
+        discovered: vec![0; n],
+        low: vec![0; n],
+        parents: vec![None; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        components: vec![0; n],
+        count: 0,
+    };
+    depth_first(graph, graph.vertices(), &mut tarjan);
+
+    // components are completed sinks first
+    let count = tarjan.count;
+    tarjan
+        .components
+        .into_iter()
+        .map(|c| count - 1 - c)
+        .collect()
+}
+
+/// The DAG of the strongly connected components of a graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condensation {
+    pub components: Vec<usize>, // component id of every vertex, in topological order
+    pub members: Vec<Vec<usize>>, // vertices of every component
+    pub dag: AdjacencyList,     // an edge between components joined by at least one edge
+}
+
+pub fn condensation<G: Graph>(graph: &G) -> Condensation {
+    let components = tarjan(graph);
+    let count = components.iter().map(|&c| c + 1).max().unwrap_or(0);
+
+    let mut members = vec![Vec::new(); count];
+    for v in graph.vertices() {
+        members[components[v]].push(v);
+    }
+
+    let mut edges = graph
+        .edges()
+        .map(|(u, v, _)| (components[u], components[v]))
+        .filter(|(a, b)| a != b)
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges.dedup();
+
+    Condensation {
+        components,
+        members,
+        dag: AdjacencyList::new_from_edges(count, edges.into_iter().map(|(a, b)| (a, b, 1))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dfs, generate};
+
+    #[test]
+    fn kosaraju_test() {
+        // two cycles, 0 -> 1 -> 2 -> 0 and 3 -> 4 -> 3, joined by 2 -> 3
+        let graph = AdjacencyList::new_from_edges(
+            5,
+            [
+                (0, 1, 1),
+                (1, 2, 1),
+                (2, 0, 1),
+                (2, 3, 1),
+                (3, 4, 1),
+                (4, 3, 1),
+            ],
+        );
+
+        let leaders = kosaraju(&graph);
+        assert!(leaders[0] == leaders[1] && leaders[1] == leaders[2]);
+        assert_eq!(leaders[3], leaders[4]);
+        assert_ne!(leaders[0], leaders[3]);
+    }
+
+    #[test]
+    fn deep_test() {
+        // one cycle through a million vertices
+        let n = 1_000_000;
+        let graph = CsrGraph::new_from_edges(n, (0..n).map(|v| (v, (v + 1) % n, ())));
+
+        let leaders = kosaraju(&graph);
+        assert!(leaders.iter().all(|&leader| leader == leaders[0]));
+    }
+
+    /// Whether two component labellings split the vertices the same way.
+    fn same_partition(a: &[usize], b: &[usize]) -> bool {
+        (0..a.len()).all(|u| (0..a.len()).all(|v| (a[u] == a[v]) == (b[u] == b[v])))
+    }
+
+    #[test]
+    fn tarjan_test() {
+        let graphs = [
+            generate::strongly_connected(80, 9, 0.03, 5).0,
+            generate::gnp(80, 0.02, true, 6),
+            generate::gnp(80, 0.05, true, 7),
+            generate::dag(80, 0.1, 8).0,
+        ];
+
+        for graph in graphs.iter() {
+            let components = tarjan(graph);
+            assert!(same_partition(&components, &kosaraju(graph)));
+            assert!(graph
+                .edges()
+                .all(|(u, v, _)| components[u] <= components[v]));
+        }
+    }
+
+    #[test]
+    fn condensation_test() {
+        // the cycles 0 1 2 and 3 4 joined by 2 -> 3, and 5 pointing into both
+        let graph = AdjacencyList::new_from_edges(
+            6,
+            [
+                (0, 1, 1),
+                (1, 2, 1),
+                (2, 0, 1),
+                (2, 3, 1),
+                (1, 3, 1),
+                (3, 4, 1),
+                (4, 3, 1),
+                (5, 0, 1),
+                (5, 4, 1),
+            ],
+        );
+
+        let condensation = condensation(&graph);
+        assert_eq!(condensation.components, vec![1, 1, 1, 2, 2, 0]);
+        assert_eq!(
+            condensation.members,
+            vec![vec![5], vec![0, 1, 2], vec![3, 4]]
+        );
+        assert_eq!(
+            condensation.dag.adj,
+            vec![vec![(1, 1), (2, 1)], vec![(2, 1)], vec![]]
+        );
+        assert_eq!(dfs::kahn(&condensation.dag), Ok(vec![0, 1, 2]));
+    }
+}
@@ -40,9 +40,9 @@ impl UnionFind {
 
         // a is the larger cluster
         let (a_leader, b_leader, a_size, b_size) = if u_size >= v_size {
-            (u_leader, v_leader, u_leader, v_size)
+            (u_leader, v_leader, u_size, v_size)
         } else {
-            (v_leader, u_leader, v_leader, u_size)
+            (v_leader, u_leader, v_size, u_size)
         };
 
         // We merge b into a
@@ -63,6 +63,14 @@ impl UnionFind {
         }
     }
 
+    /// Number of vertices in the cluster of `u`
+    pub fn cluster_size(&self, u: usize) -> usize {
+        match self.map[self.find(u)] {
+            Desc::Size(x) => x,
+            Desc::Leader(_) => unreachable!("a leader always stores its size"),
+        }
+    }
+
     pub fn n_clusters(&self) -> usize {
         self.clusters.len()
     }
@@ -71,9 +79,9 @@ impl UnionFind {
         self.map
             .iter()
             .enumerate()
-            .map(|(i, d)| match d {
-                &Desc::Leader(x) => x,
-                &Desc::Size(_) => i,
+            .map(|(i, d)| match *d {
+                Desc::Leader(x) => x,
+                Desc::Size(_) => i,
             })
             .collect()
     }
@@ -98,7 +106,24 @@ mod tests {
 
         uf.union(2, 3);
         assert!(uf.find(3) == 0 && uf.find(1) == 0 && uf.clusters[&0].len() == 4);
+        assert_eq!(uf.cluster_size(2), 4);
 
         dbg!(&uf);
     }
+
+    #[test]
+    fn size_test() {
+        let mut uf = UnionFind::new(5);
+
+        // the size of the merged cluster used to start from the leader's
+        // index rather than its size
+        uf.union(3, 4);
+        assert_eq!(uf.cluster_size(4), 2);
+
+        uf.union(1, 4);
+        assert_eq!(uf.find(1), 3);
+        assert_eq!(uf.cluster_size(1), 3);
+        assert_eq!(uf.cluster_size(0), 1);
+        assert_eq!(uf.n_clusters(), 3);
+    }
 }
@@ -0,0 +1,144 @@
+//! Edge weights the shortest path and spanning tree algorithms are generic
+//! over: the integers, and floats wrapped in `OrderedFloat`.
+
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Add, Sub},
+};
+
+pub trait Weight: Copy + Ord + Debug {
+    const ZERO: Self;
+    /// Larger than any path: the distance to a vertex that can't be reached.
+    const INFINITY: Self;
+
+    /// Infinity plus anything is infinity, and every other sum is clamped to
+    /// the range of the type instead of overflowing.
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+macro_rules! integer_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                const ZERO: Self = 0;
+                const INFINITY: Self = <$t>::MAX;
+
+                fn saturating_add(self, other: Self) -> Self {
+                    if self == Self::INFINITY || other == Self::INFINITY {
+                        Self::INFINITY
+                    } else {
+                        <$t>::saturating_add(self, other)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer_weight!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A float ordered by `total_cmp`, so that it can be sorted on and keyed in a
+/// priority queue. NaN sorts above infinity and shouldn't be used as a weight.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrderedFloat<F>(pub F);
+
+macro_rules! float_weight {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq for OrderedFloat<$t> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+
+            impl Eq for OrderedFloat<$t> {}
+
+            impl PartialOrd for OrderedFloat<$t> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for OrderedFloat<$t> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+            }
+
+            impl Add for OrderedFloat<$t> {
+                type Output = Self;
+
+                fn add(self, other: Self) -> Self {
+                    OrderedFloat(self.0 + other.0)
+                }
+            }
+
+            impl Sub for OrderedFloat<$t> {
+                type Output = Self;
+
+                fn sub(self, other: Self) -> Self {
+                    OrderedFloat(self.0 - other.0)
+                }
+            }
+
+            impl From<$t> for OrderedFloat<$t> {
+                fn from(f: $t) -> Self {
+                    OrderedFloat(f)
+                }
+            }
+
+            impl Weight for OrderedFloat<$t> {
+                const ZERO: Self = OrderedFloat(0.0);
+                const INFINITY: Self = OrderedFloat(<$t>::INFINITY);
+
+                // a sum of finite floats already rounds to infinity on overflow
+                fn saturating_add(self, other: Self) -> Self {
+                    if self == Self::INFINITY || other == Self::INFINITY {
+                        Self::INFINITY
+                    } else {
+                        self + other
+                    }
+                }
+            }
+        )*
+    };
+}
+
+float_weight!(f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_test() {
+        assert_eq!(Weight::saturating_add(3usize, 4), 7);
+        assert_eq!(Weight::saturating_add(usize::INFINITY, 1), usize::INFINITY);
+        // unlike `isize::saturating_add`, which would make it finite again
+        assert_eq!(Weight::saturating_add(isize::INFINITY, -5), isize::INFINITY);
+        assert_eq!(Weight::saturating_add(isize::MAX - 1, 5), isize::INFINITY);
+        assert_eq!(Weight::saturating_add(isize::MIN, -5), isize::MIN);
+    }
+
+    #[test]
+    fn float_test() {
+        let mut weights = [2.5, -1.0, f64::INFINITY, 0.0].map(OrderedFloat);
+        weights.sort();
+        assert_eq!(weights, [-1.0, 0.0, 2.5, f64::INFINITY].map(OrderedFloat));
+
+        let infinity = OrderedFloat::<f32>::INFINITY;
+        assert_eq!(
+            infinity.saturating_add(OrderedFloat(f32::NEG_INFINITY)),
+            infinity
+        );
+        assert_eq!(
+            OrderedFloat(1.5f32).saturating_add(OrderedFloat(2.0)),
+            OrderedFloat(3.5)
+        );
+        assert_eq!(
+            OrderedFloat::from(0.5) + OrderedFloat(1.0),
+            OrderedFloat(1.5)
+        );
+    }
+}